@@ -4,12 +4,12 @@
 //! drives the timer, and prints both the results the client gets back
 //! and the notifications the server pushes on its own.
 
-use std::{env, fs, thread, time::Duration};
+use std::{env, fs, sync::Arc, thread, time::Duration};
 
 use comodoro::{
     client::std::TimerClient,
-    server::std::TimerServer,
-    timer::{TimerCycle, TimerLoop, TimerSchedule},
+    server::{clock::SystemClock, std::TimerServer},
+    timer::{TimerCycle, TimerJump, TimerLoop, TimerSchedule},
     transport::TimerAddress,
 };
 
@@ -18,7 +18,7 @@ fn main() {
     let _ = fs::remove_file(&path);
 
     let schedule = TimerSchedule {
-        cycles: vec![TimerCycle::new("Work", 2), TimerCycle::new("Rest", 2)],
+        cycles: vec![TimerCycle::new("Work", 2000), TimerCycle::new("Rest", 2000)],
         loops: TimerLoop::Infinite,
     };
 
@@ -27,6 +27,12 @@ fn main() {
     let events = TimerServer {
         schedule,
         addresses: vec![address.clone()],
+        ticks: false,
+        jump: TimerJump::Count,
+        clock: Arc::new(SystemClock),
+        store: None,
+        history: None,
+        goal: None,
     }
     .serve()
     .expect("serve timer");
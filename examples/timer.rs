@@ -9,18 +9,18 @@ use comodoro::timer::{Timer, TimerCycle, TimerLoop, TimerSchedule};
 
 fn main() {
     let schedule = TimerSchedule {
-        cycles: vec![TimerCycle::new("Work", 3), TimerCycle::new("Rest", 2)],
+        cycles: vec![TimerCycle::new("Work", 3000), TimerCycle::new("Rest", 2000)],
         loops: TimerLoop::Fixed(2),
     };
 
     let mut timer = Timer::new(schedule);
 
     println!("start at t=0:");
-    for event in timer.start(0) {
+    for event in timer.start(0).unwrap() {
         println!("  {event:?}");
     }
 
-    for now in 1..=10 {
+    for now in (1000..=10_000).step_by(1000) {
         let events: Vec<_> = timer.update(now).into_iter().collect();
 
         if events.is_empty() {
@@ -28,7 +28,7 @@ fn main() {
         }
 
         println!(
-            "tick at t={now}: [{}] {}s left",
+            "tick at t={now}ms: [{}] {}ms left",
             timer.cycle.name, timer.cycle.duration
         );
         for event in events {
@@ -37,7 +37,7 @@ fn main() {
     }
 
     println!("stop:");
-    for event in timer.stop() {
+    for event in timer.stop().unwrap() {
         println!("  {event:?}");
     }
 }
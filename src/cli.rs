@@ -15,13 +15,14 @@
 //! command runs against, [`configure`] the wizard writing a document,
 //! [`transport`] the selection of the one a command talks over,
 //! [`hook`] the reactions bound to timer events, [`json_schema`] the
-//! schemas of what the commands print, and [`client`] and [`server`]
-//! one module per command.
+//! schemas of what the commands print, and [`client`], [`server`] and
+//! [`history`] one module per command.
 
 pub mod account;
 pub mod client;
 pub mod config;
 pub mod configure;
+pub mod history;
 pub mod hook;
 pub mod json_schema;
 pub mod server;
@@ -52,12 +53,14 @@ use pimalaya_config::toml::TomlConfig;
 use crate::cli::{
     account::Account,
     client::{
-        get::TimerGetCommand, pause::TimerPauseCommand, resume::TimerResumeCommand,
-        set::TimerSetCommand, start::TimerStartCommand, stop::TimerStopCommand,
-        watch::TimerWatchCommand,
+        extend::TimerExtendCommand, get::TimerGetCommand, goto::TimerGotoCommand,
+        interrupt::TimerInterruptCommand, label::TimerLabelCommand, pause::TimerPauseCommand,
+        resume::TimerResumeCommand, set::TimerSetCommand, skip::TimerSkipCommand,
+        start::TimerStartCommand, stop::TimerStopCommand, watch::TimerWatchCommand,
     },
     config::{CONFIG_SAMPLE_URL, Config},
     configure::ConfigureCommand,
+    history::{export::HistoryExportCommand, report::HistoryReportCommand},
     server::TimerServerCommand,
 };
 
@@ -114,6 +117,20 @@ pub enum Command {
     Stop(TimerStopCommand),
     /// Set the remaining duration of the current cycle.
     Set(TimerSetCommand),
+    /// Extend the current cycle.
+    Extend(TimerExtendCommand),
+    /// Skip to the next cycle.
+    Skip(TimerSkipCommand),
+    /// Go to a given cycle.
+    Goto(TimerGotoCommand),
+    /// Label the timer with a task.
+    Label(TimerLabelCommand),
+    /// Record an interruption of the current cycle.
+    Interrupt(TimerInterruptCommand),
+    /// Report the cycles the server recorded.
+    Report(HistoryReportCommand),
+    /// Export the cycles the server recorded.
+    Export(HistoryExportCommand),
     /// Generate the shell completion scripts.
     #[command(arg_required_else_help = true, alias = "cpl")]
     Completion(CompletionCommand),
@@ -199,6 +216,26 @@ impl Command {
                 let account = take_account(printer, config_paths, account_name)?;
                 cmd.execute(printer, &account)
             }
+            Self::Extend(cmd) => {
+                let account = take_account(printer, config_paths, account_name)?;
+                cmd.execute(printer, &account)
+            }
+            Self::Skip(cmd) => {
+                let account = take_account(printer, config_paths, account_name)?;
+                cmd.execute(printer, &account)
+            }
+            Self::Goto(cmd) => {
+                let account = take_account(printer, config_paths, account_name)?;
+                cmd.execute(printer, &account)
+            }
+            Self::Label(cmd) => {
+                let account = take_account(printer, config_paths, account_name)?;
+                cmd.execute(printer, &account)
+            }
+            Self::Interrupt(cmd) => {
+                let account = take_account(printer, config_paths, account_name)?;
+                cmd.execute(printer, &account)
+            }
             Self::Pause(cmd) => {
                 let account = take_account(printer, config_paths, account_name)?;
                 cmd.execute(printer, &account)
@@ -211,6 +248,14 @@ impl Command {
                 let account = take_account(printer, config_paths, account_name)?;
                 cmd.execute(printer, &account)
             }
+            Self::Report(cmd) => {
+                let account = take_account(printer, config_paths, account_name)?;
+                cmd.execute(printer, &account)
+            }
+            Self::Export(cmd) => {
+                let account = take_account(printer, config_paths, account_name)?;
+                cmd.execute(printer, &account)
+            }
 
             Self::Completion(cmd) => cmd.execute(printer, Cli::command()),
             Self::Manual(cmd) => cmd.execute(printer, Cli::command()),
@@ -21,8 +21,13 @@ use alloc::{string::String, vec, vec::Vec};
 use std::collections::HashMap;
 
 use crate::{
-    cli::{config::AccountConfig, hook::TimerHook, transport::Transport},
-    timer::{TimerLoop, TimerPrecision, TimerSchedule},
+    cli::{
+        config::{AccountConfig, HistoryConfig, StateConfig},
+        hook::TimerHook,
+        transport::Transport,
+    },
+    server::{goal::TimerGoal, history::TimerHistory, store::TimerStore},
+    timer::{TimerEntry, TimerJump, TimerLoop, TimerPrecision, TimerSchedule},
     transport::TimerAddress,
 };
 
@@ -33,6 +38,14 @@ pub struct Account {
     pub schedule: TimerSchedule,
     /// How precisely a client renders the remaining duration.
     pub precision: TimerPrecision,
+    /// What the server does with the time the clock jumps over.
+    pub clock_jump: TimerJump,
+    /// Where the server keeps its timer across restarts, if anywhere.
+    pub state: Option<TimerStore>,
+    /// Where the server records the cycles it ran, if anywhere.
+    pub history: Option<TimerHistory>,
+    /// What the server counts a day towards, if anything.
+    pub goal: Option<TimerGoal>,
     /// The hooks to run, by event name.
     pub hooks: HashMap<String, TimerHook>,
     /// Where the local socket is.
@@ -83,6 +96,10 @@ impl From<AccountConfig> for Account {
             cycles,
             cycles_count,
             precision,
+            clock_jump,
+            state,
+            history,
+            goal,
             hooks,
         } = config;
 
@@ -95,15 +112,24 @@ impl From<AccountConfig> for Account {
             Transport::UnixSocket
         };
 
-        Self {
-            schedule: TimerSchedule {
-                cycles,
-                loops: match cycles_count {
-                    Some(count) => TimerLoop::Fixed(count),
-                    None => TimerLoop::Infinite,
-                },
+        let schedule = TimerSchedule {
+            cycles: TimerEntry::flatten(&cycles),
+            loops: match cycles_count {
+                Some(count) => TimerLoop::Fixed(count),
+                None => TimerLoop::Infinite,
             },
+        };
+
+        let first_cycle = schedule.cycles.first().map_or("", |cycle| &cycle.name);
+        let goal = goal.as_ref().map(|goal| goal.goal(first_cycle));
+
+        Self {
+            schedule,
             precision,
+            clock_jump,
+            state: state.as_ref().map(StateConfig::store),
+            history: history.as_ref().map(HistoryConfig::history),
+            goal,
             hooks,
             socket: socket.address(),
             tcp: tcp.address(),
@@ -0,0 +1,138 @@
+//! Command extending the current cycle.
+
+// NOTE: the clap derive expands to `format!` when parsing the delta
+// value, which no_std does not put in scope.
+use alloc::{format, string::String};
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_cli::printer::{Message, Printer};
+
+use crate::{
+    cli::{account::Account, transport::TransportArg},
+    client::std::TimerClient,
+};
+
+/// Extend the current cycle.
+///
+/// This command adds time to the cycle the timer is currently running,
+/// or removes some when negative. Unlike `set`, the cycle may end up
+/// longer than configured: the cycles after it start later instead.
+#[derive(Debug, Parser)]
+pub struct TimerExtendCommand {
+    /// The time to add, or to remove when prefixed with `-`.
+    ///
+    /// Either a number of seconds, or amounts suffixed with `h`, `m`
+    /// or `s` and written together, like `+10m` or `-1h30m`.
+    #[arg(name = "delta", value_name = "DELTA")]
+    #[arg(allow_hyphen_values = true, value_parser = delta_parser)]
+    pub delta: isize,
+    // NOTE: the transport comes last, for the same reason as in `set`.
+    /// The transport used to reach the server.
+    #[command(flatten)]
+    pub transport: TransportArg,
+}
+
+impl TimerExtendCommand {
+    /// Extends the current cycle of the timer the server owns.
+    pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
+        let address = account.address(self.transport.transport);
+        TimerClient::connect(&address)?.extend(self.delta)?;
+        printer.out(Message::new("Timer cycle successfully extended"))
+    }
+}
+
+/// Parses a signed delta, in seconds or in amounts of hours, minutes
+/// and seconds.
+fn delta_parser(delta: &str) -> Result<isize, String> {
+    let (sign, amounts) = match delta.strip_prefix('-') {
+        Some(amounts) => (-1, amounts),
+        None => (1, delta.strip_prefix('+').unwrap_or(delta)),
+    };
+
+    let invalid = || format!("invalid delta `{delta}`, expected e.g. +10m or -90");
+    let overflow = || format!("delta `{delta}` is out of range");
+
+    // NOTE: the amounts are parsed as digits alone, so a second sign,
+    // as in `+-5`, is refused rather than read as the one it carries.
+    if amounts.starts_with(['+', '-']) {
+        return Err(invalid());
+    }
+
+    if let Ok(secs) = amounts.parse::<isize>() {
+        return Ok(sign * secs);
+    }
+
+    let mut secs: isize = 0;
+    let mut digits = String::new();
+
+    for c in amounts.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("unknown unit `{c}`, expected h, m or s")),
+        };
+
+        if digits.is_empty() {
+            return Err(format!("missing amount before `{c}`"));
+        }
+
+        let amount: isize = digits.parse().map_err(|_| overflow())?;
+
+        secs = amount
+            .checked_mul(unit)
+            .and_then(|amount| secs.checked_add(amount))
+            .ok_or_else(overflow)?;
+        digits.clear();
+    }
+
+    if !digits.is_empty() || amounts.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(sign * secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::delta_parser;
+
+    #[test]
+    fn a_delta_reads_as_seconds_or_as_amounts() {
+        assert_eq!(delta_parser("90"), Ok(90));
+        assert_eq!(delta_parser("+10m"), Ok(600));
+        assert_eq!(delta_parser("-1h30m"), Ok(-5400));
+        assert_eq!(delta_parser("1m30s"), Ok(90));
+    }
+
+    #[test]
+    fn a_doubled_sign_is_refused() {
+        assert!(delta_parser("+-5").is_err());
+        assert!(delta_parser("--5").is_err());
+        assert!(delta_parser("-+5m").is_err());
+    }
+
+    #[test]
+    fn a_delta_too_large_to_count_is_refused() {
+        let err = delta_parser(&format!("{}h", isize::MAX / 60)).unwrap_err();
+        assert!(err.contains("out of range"), "{err}");
+        assert!(delta_parser("99999999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn a_delta_without_amount_is_refused() {
+        assert!(delta_parser("").is_err());
+        assert!(delta_parser("-").is_err());
+        assert!(delta_parser("m").is_err());
+        assert!(delta_parser("10x").is_err());
+        assert!(delta_parser("10m5").is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! Command moving the timer to a given cycle.
+
+// NOTE: the clap derive expands to `format!` when parsing the cycle
+// and loop values, which no_std does not put in scope.
+use alloc::format;
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_cli::printer::{Message, Printer};
+
+use crate::{
+    cli::{account::Account, transport::TransportArg},
+    client::std::TimerClient,
+    timer::TimerCycleRef,
+};
+
+/// Go to a given cycle.
+///
+/// This command moves the timer to the start of a cycle of its
+/// schedule, starting it there when it is stopped. Suited to resuming
+/// where a restarted server left off.
+#[derive(Debug, Parser)]
+pub struct TimerGotoCommand {
+    /// The cycle to go to, by zero-based index or by name.
+    ///
+    /// A name goes to the first cycle carrying it.
+    #[arg(name = "cycle", value_name = "CYCLE")]
+    pub cycle: TimerCycleRef,
+    /// The zero-based loop to go to, the current one when omitted.
+    #[arg(long = "loop", short, value_name = "LOOP")]
+    pub loop_index: Option<usize>,
+    // NOTE: the transport comes last, for the same reason as in `set`.
+    /// The transport used to reach the server.
+    #[command(flatten)]
+    pub transport: TransportArg,
+}
+
+impl TimerGotoCommand {
+    /// Moves the timer the server owns to the given cycle.
+    pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
+        let address = account.address(self.transport.transport);
+        TimerClient::connect(&address)?.goto(self.cycle, self.loop_index)?;
+        printer.out(Message::new("Timer cycle successfully changed"))
+    }
+}
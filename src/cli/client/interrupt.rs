@@ -0,0 +1,58 @@
+//! Command recording an interruption of the current cycle.
+
+use alloc::string::String;
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_cli::printer::{Message, Printer};
+
+use crate::{
+    cli::{account::Account, transport::TransportArg},
+    client::std::TimerClient,
+    timer::{TimerInterruption, TimerInterruptionKind},
+};
+
+/// Record an interruption of the current cycle.
+///
+/// This command counts one interruption against the cycle the timer
+/// runs, the way the Pomodoro Technique tallies them: internal when
+/// you broke off on your own, external when someone or something broke
+/// in. The cycle carries them until it ends, and the history records
+/// them along with it.
+#[derive(Debug, Parser)]
+pub struct TimerInterruptCommand {
+    /// Whether someone or something else caused the interruption.
+    #[arg(long, short)]
+    pub external: bool,
+    /// What the interruption was about.
+    ///
+    /// An empty note counts as none, which is how an interruption
+    /// without one names a transport.
+    #[arg(name = "note", value_name = "NOTE")]
+    pub note: Option<String>,
+    // NOTE: the transport comes last, like in `set`: clap fills the
+    // positionals in order, and the note is the one given most.
+    /// The transport used to reach the server.
+    #[command(flatten)]
+    pub transport: TransportArg,
+}
+
+impl TimerInterruptCommand {
+    /// Records the interruption on the timer the server owns.
+    pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
+        let kind = if self.external {
+            TimerInterruptionKind::External
+        } else {
+            TimerInterruptionKind::Internal
+        };
+
+        let interruption = TimerInterruption {
+            kind,
+            note: self.note.filter(|note| !note.is_empty()),
+        };
+
+        let address = account.address(self.transport.transport);
+        TimerClient::connect(&address)?.interrupt(interruption)?;
+        printer.out(Message::new("Timer interruption successfully recorded"))
+    }
+}
@@ -0,0 +1,59 @@
+//! Command labeling the timer with a task.
+
+use alloc::{string::String, vec::Vec};
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_cli::printer::{Message, Printer};
+
+use crate::{
+    cli::{account::Account, transport::TransportArg},
+    client::std::TimerClient,
+    protocol::TimerLabelParams,
+};
+
+/// Label the timer with a task.
+///
+/// This command tells the server what the timer runs for, replacing
+/// the task and tags it carried. Every cycle carries them until the
+/// timer stops: they show next to the timer, reach the hooks, and are
+/// recorded in the history. Given neither, the timer runs for nothing
+/// in particular again.
+#[derive(Debug, Parser)]
+pub struct TimerLabelCommand {
+    /// The task and tags to label the timer with.
+    #[command(flatten)]
+    pub label: LabelArg,
+    /// The transport used to reach the server.
+    #[command(flatten)]
+    pub transport: TransportArg,
+}
+
+impl TimerLabelCommand {
+    /// Labels the timer the server owns.
+    pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
+        let address = account.address(self.transport.transport);
+        TimerClient::connect(&address)?.label(self.label.into())?;
+        printer.out(Message::new("Timer successfully labeled"))
+    }
+}
+
+/// The task arguments `label` and `start` share.
+#[derive(Debug, Default, Parser)]
+pub struct LabelArg {
+    /// The free-text task the timer runs for.
+    #[arg(long, value_name = "TASK")]
+    pub task: Option<String>,
+    /// A tag of the task. Can be given several times.
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+}
+
+impl From<LabelArg> for TimerLabelParams {
+    fn from(label: LabelArg) -> Self {
+        Self {
+            task: label.task,
+            tags: label.tags,
+        }
+    }
+}
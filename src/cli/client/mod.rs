@@ -6,10 +6,15 @@
 //! they all share in [`crate::cli::transport`], since the server
 //! commands select a transport too.
 
+pub mod extend;
 pub mod get;
+pub mod goto;
+pub mod interrupt;
+pub mod label;
 pub mod pause;
 pub mod resume;
 pub mod set;
+pub mod skip;
 pub mod start;
 pub mod stop;
 pub mod timer;
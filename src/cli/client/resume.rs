@@ -11,7 +11,9 @@ use crate::{
 
 /// Resume the timer.
 ///
-/// This command sends a resume request to the server.
+/// This command sends a resume request to the server. A timer waiting
+/// at the end of a cycle, or counting overtime past it, begins the
+/// next cycle instead.
 #[derive(Debug, Parser)]
 pub struct TimerResumeCommand {
     /// The transport used to reach the server.
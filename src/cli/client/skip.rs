@@ -0,0 +1,30 @@
+//! Command skipping the current cycle.
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_cli::printer::{Message, Printer};
+
+use crate::{
+    cli::{account::Account, transport::TransportArg},
+    client::std::TimerClient,
+};
+
+/// Skip the current cycle.
+///
+/// This command ends the cycle the timer is currently running and
+/// begins the next one, firing the same hooks a natural ending would.
+#[derive(Debug, Parser)]
+pub struct TimerSkipCommand {
+    /// The transport used to reach the server.
+    #[command(flatten)]
+    pub transport: TransportArg,
+}
+
+impl TimerSkipCommand {
+    /// Skips the current cycle of the timer the server owns.
+    pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
+        let address = account.address(self.transport.transport);
+        TimerClient::connect(&address)?.skip()?;
+        printer.out(Message::new("Timer cycle successfully skipped"))
+    }
+}
@@ -5,16 +5,26 @@ use clap::Parser;
 use pimalaya_cli::printer::{Message, Printer};
 
 use crate::{
-    cli::{account::Account, transport::TransportArg},
+    cli::{account::Account, client::label::LabelArg, transport::TransportArg},
     client::std::TimerClient,
+    timer::TimerCycleRef,
 };
 
 /// Start the timer.
 ///
-/// This command sends a start request to the server.
+/// This command sends a start request to the server, labeling the run
+/// with the task it is for when given one.
 #[derive(Debug, Parser)]
 pub struct TimerStartCommand {
-    /// The transport used to reach the server.
+    /// Start at the given cycle rather than at the first one.
+    ///
+    /// Takes a zero-based index or a name, like `comodoro goto`, and
+    /// moves a timer that is already running to that cycle.
+    #[arg(long, value_name = "CYCLE")]
+    pub at: Option<TimerCycleRef>,
+    /// The task and tags to label the run with.
+    #[command(flatten)]
+    pub label: LabelArg,
     /// The transport used to reach the server.
     #[command(flatten)]
     pub transport: TransportArg,
@@ -24,7 +34,21 @@ impl TimerStartCommand {
     /// Starts the timer the server owns.
     pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
         let address = account.address(self.transport.transport);
-        TimerClient::connect(&address)?.start()?;
+        let mut client = TimerClient::connect(&address)?;
+
+        match self.at {
+            // NOTE: a goto cannot label, so a label given along `--at`
+            // goes first, which a stopped timer keeps for the start.
+            Some(cycle) => {
+                if self.label.task.is_some() || !self.label.tags.is_empty() {
+                    client.label(self.label.into())?;
+                }
+
+                client.goto(cycle, None)?
+            }
+            None => client.start_labeled(self.label.into())?,
+        };
+
         printer.out(Message::new("Timer successfully started"))
     }
 }
@@ -25,6 +25,8 @@ pub struct DisplayTimer<'a> {
 impl fmt::Display for DisplayTimer<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.fmt_state(f)?;
+        self.fmt_task(f)?;
+        self.fmt_goal(f)?;
         // NOTE: the newline is what makes the line-buffered stdout
         // flush, without which `watch` would print nothing until it
         // exits. It also separates consecutive outputs.
@@ -40,36 +42,102 @@ impl DisplayTimer<'_> {
         match timer.state {
             TimerState::Stopped => write!(f, "OFF"),
             TimerState::Paused => write!(f, "[{cycle}] paused"),
-            TimerState::Running if timer.cycle.duration < 60 => {
-                write!(f, "[{cycle}] {}s", timer.cycle.duration)
+            TimerState::Waiting => write!(f, "[{cycle}] waiting"),
+            // NOTE: a count-up cycle shows the time it ran, like
+            // overtime does, since it has no time remaining to show.
+            TimerState::Running if timer.counting.is_none() => {
+                write!(f, "[{cycle}] ")?;
+                self.fmt_duration(f)
             }
-            TimerState::Running if timer.cycle.duration < 3600 => match self.account.precision {
-                TimerPrecision::Second => write!(
-                    f,
-                    "[{cycle}] {}min {}s",
-                    timer.cycle.duration / 60,
-                    timer.cycle.duration % 60
-                ),
+            TimerState::Running | TimerState::Overtime => {
+                write!(f, "[{cycle}] +")?;
+                self.fmt_duration(f)
+            }
+        }
+    }
+
+    /// Writes the task the timer runs for and its tags, as in
+    /// `[Work] 12min · review PR 42 #review`.
+    fn fmt_task(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cycle = &self.timer.cycle;
+
+        if let TimerState::Stopped = self.timer.state {
+            return Ok(());
+        }
+
+        if cycle.task.is_none() && cycle.tags.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, " ·")?;
+
+        if let Some(task) = &cycle.task {
+            write!(f, " {task}")?;
+        }
+
+        for tag in &cycle.tags {
+            write!(f, " #{tag}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the progress towards the daily goal, as in
+    /// `[Work] 12min · goal 3/8 · 75/300min`, or that it is reached.
+    fn fmt_goal(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(goal) = &self.timer.goal else {
+            return Ok(());
+        };
+
+        if goal.reached {
+            return write!(f, " · goal reached");
+        }
+
+        write!(f, " · goal")?;
+
+        if let Some(target) = goal.target_cycles {
+            write!(f, " {}/{target}", goal.cycles)?;
+        }
+
+        if let Some(target) = goal.target_duration_ms {
+            if goal.target_cycles.is_some() {
+                write!(f, " ·")?;
+            }
+
+            write!(f, " {}/{}min", goal.duration_ms / 60_000, target / 60_000)?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_duration(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let duration = self.timer.cycle.duration / 1000;
+
+        if duration < 60 {
+            return write!(f, "{duration}s");
+        }
+
+        if duration < 3600 {
+            return match self.account.precision {
+                TimerPrecision::Second => write!(f, "{}min {}s", duration / 60, duration % 60),
                 TimerPrecision::Minute | TimerPrecision::Hour => {
-                    write!(f, "[{cycle}] {}min", timer.cycle.duration / 60)
+                    write!(f, "{}min", duration / 60)
                 }
-            },
-            TimerState::Running => match self.account.precision {
-                TimerPrecision::Second => write!(
-                    f,
-                    "[{cycle}] {}h {}min {}s",
-                    timer.cycle.duration / 3600,
-                    (timer.cycle.duration % 3600) / 60,
-                    (timer.cycle.duration % 3600) % 60,
-                ),
-                TimerPrecision::Minute => write!(
-                    f,
-                    "[{cycle}] {}h {}min",
-                    timer.cycle.duration / 3600,
-                    (timer.cycle.duration % 3600) / 60,
-                ),
-                TimerPrecision::Hour => write!(f, "[{cycle}] {}h", timer.cycle.duration / 3600),
-            },
+            };
+        }
+
+        match self.account.precision {
+            TimerPrecision::Second => write!(
+                f,
+                "{}h {}min {}s",
+                duration / 3600,
+                (duration % 3600) / 60,
+                (duration % 3600) % 60,
+            ),
+            TimerPrecision::Minute => {
+                write!(f, "{}h {}min", duration / 3600, (duration % 3600) / 60)
+            }
+            TimerPrecision::Hour => write!(f, "{}h", duration / 3600),
         }
     }
 }
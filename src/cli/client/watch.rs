@@ -1,5 +1,7 @@
 //! Command printing the timer on every change.
 
+use alloc::{string::String, vec::Vec};
+
 use anyhow::Result;
 use clap::Parser;
 use pimalaya_cli::printer::Printer;
@@ -7,6 +9,7 @@ use pimalaya_cli::printer::Printer;
 use crate::{
     cli::{account::Account, client::timer::DisplayTimer, transport::TransportArg},
     client::std::TimerClient,
+    protocol::{TimerEventFilter, TimerSubscribeParams, TimerTicks},
 };
 
 /// Watch the timer.
@@ -14,8 +17,22 @@ use crate::{
 /// This command subscribes to the server and prints the timer state
 /// every time it changes, until interrupted. Suited to a status bar,
 /// which no longer has to poll.
+///
+/// The server only sends the ticks changing what the account precision
+/// shows, so a status bar at minute precision wakes once a minute
+/// rather than every second. Given event kinds, it only wakes for
+/// those, such as `began,ended` for the transitions between cycles
+/// without any tick.
 #[derive(Debug, Parser)]
 pub struct TimerWatchCommand {
+    /// The event kinds to watch, comma-separated, named after their
+    /// notification without the `timer.` prefix.
+    #[arg(long, value_name = "KIND", value_delimiter = ',')]
+    pub events: Vec<String>,
+    /// Receive a tick every given number of seconds instead of at
+    /// every change the account precision shows.
+    #[arg(long, value_name = "SECS")]
+    pub every: Option<usize>,
     /// The transport used to reach the server.
     /// The transport used to reach the server.
     #[command(flatten)]
@@ -27,7 +44,17 @@ impl TimerWatchCommand {
     pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
         let address = account.address(self.transport.transport);
         let mut client = TimerClient::connect(&address)?;
-        client.subscribe()?;
+        client.subscribe_filtered(TimerSubscribeParams {
+            include: TimerEventFilter {
+                events: self.events,
+                cycles: Vec::new(),
+            },
+            ticks: Some(match self.every {
+                Some(seconds) => TimerTicks::Every(seconds),
+                None => TimerTicks::Precision(account.precision.clone()),
+            }),
+            ..Default::default()
+        })?;
 
         let mut last = client.get()?;
         printer.out(DisplayTimer {
@@ -22,11 +22,19 @@ use alloc::{
 use std::{collections::HashMap, path::PathBuf};
 
 use pimalaya_config::toml::TomlConfig;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, de::Error};
 
 use crate::{
-    cli::hook::TimerHook,
-    timer::{TimerCycle, TimerPrecision},
+    cli::{
+        history::{Time, UtcOffset},
+        hook::TimerHook,
+    },
+    server::{
+        goal::TimerGoal,
+        history::TimerHistory,
+        store::{TimerRestore, TimerStore},
+    },
+    timer::{TimerCycle, TimerEntry, TimerGroup, TimerJump, TimerPrecision},
     transport::{TimerAddress, default_socket_path},
 };
 
@@ -89,14 +97,32 @@ pub struct AccountConfig {
     /// a port on an account that says nothing about TCP.
     #[serde(default)]
     pub tcp: TcpConfig,
-    /// The ordered cycles the timer runs through.
-    pub cycles: Vec<TimerCycle>,
+    /// The ordered cycles the timer runs through, some of them maybe
+    /// grouped and repeated.
+    ///
+    /// Refused when they flatten into no cycle at all, or hold a group
+    /// repeated no time or holding no entry, since a timer needs a
+    /// cycle to start from.
+    #[serde(deserialize_with = "cycles")]
+    pub cycles: Vec<TimerEntry>,
     /// How many full loops the timer runs before stopping, unbounded
     /// when absent.
     pub cycles_count: Option<usize>,
     /// How precisely the remaining duration is displayed.
     #[serde(default)]
     pub precision: TimerPrecision,
+    /// What the timer does with the time the clock jumps over, such
+    /// as the time the machine spends suspended.
+    #[serde(default)]
+    pub clock_jump: TimerJump,
+    /// Where the server keeps its timer across restarts, nowhere when
+    /// absent.
+    pub state: Option<StateConfig>,
+    /// Where the server records the cycles it ran, nowhere when
+    /// absent.
+    pub history: Option<HistoryConfig>,
+    /// What the server counts a day towards, nothing when absent.
+    pub goal: Option<GoalConfig>,
     /// The hooks to run, by event name.
     #[serde(default)]
     pub hooks: HashMap<String, TimerHook>,
@@ -132,10 +158,8 @@ impl AccountConfig {
 
         document.push_str("cycles = [\n");
 
-        for TimerCycle { name, duration } in &self.cycles {
-            document.push_str(&format!(
-                "  {{ name = \"{name}\", duration = {duration} }},\n"
-            ));
+        for entry in &self.cycles {
+            document.push_str(&format!("  {},\n", render_entry(entry)));
         }
 
         document.push_str("]\n");
@@ -205,6 +229,80 @@ impl Default for SocketConfig {
     }
 }
 
+/// Where a server keeps its timer across restarts.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct StateConfig {
+    /// The file the timer is saved to.
+    pub path: PathBuf,
+    /// How a timer that was running is taken back.
+    #[serde(default)]
+    pub restore: TimerRestore,
+    /// How old, in seconds, a saved timer can be and still be taken
+    /// back, with no limit when absent.
+    pub stale_after: Option<u64>,
+}
+
+impl StateConfig {
+    /// The store this configuration describes.
+    pub fn store(&self) -> TimerStore {
+        TimerStore {
+            path: self.path.clone(),
+            restore: self.restore,
+            stale_after: self.stale_after.map(|secs| secs.saturating_mul(1000)),
+        }
+    }
+}
+
+/// Where a server records the cycles it ran.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct HistoryConfig {
+    /// The JSON Lines file the records are appended to.
+    pub path: PathBuf,
+}
+
+impl HistoryConfig {
+    /// The history this configuration describes.
+    pub fn history(&self) -> TimerHistory {
+        TimerHistory {
+            path: self.path.clone(),
+        }
+    }
+}
+
+/// What a server counts a day towards.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct GoalConfig {
+    /// The name of the cycles counted, the first one of the account
+    /// when absent.
+    pub cycle: Option<String>,
+    /// How many of those cycles a day should see run out.
+    pub cycles: Option<usize>,
+    /// How long, in seconds, they should run in a day.
+    pub duration: Option<u64>,
+    /// The time of day at which a day begins, midnight by default.
+    #[serde(default)]
+    pub day_start: Time,
+    /// The offset from UTC of that time of day, UTC by default.
+    #[serde(default)]
+    pub utc_offset: UtcOffset,
+}
+
+impl GoalConfig {
+    /// The goal this configuration describes, counting `default_cycle`
+    /// when it names no cycle.
+    pub fn goal(&self, default_cycle: &str) -> TimerGoal {
+        TimerGoal {
+            cycle: self.cycle.as_deref().unwrap_or(default_cycle).to_string(),
+            cycles: self.cycles,
+            duration: self.duration.map(|secs| secs.saturating_mul(1000) as usize),
+            day_start: self.day_start.ms() - self.utc_offset.0 * 60_000,
+        }
+    }
+}
+
 /// Where a client and a server meet over TCP.
 ///
 /// The listener it describes is unauthenticated, so whoever reaches the
@@ -260,3 +358,94 @@ fn localhost() -> String {
 fn tcp_port() -> u16 {
     TCP_PORT
 }
+
+/// Reads the `cycles` of an account, refusing a schedule that would run
+/// no cycle.
+fn cycles<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<TimerEntry>, D::Error> {
+    let cycles = Vec::<TimerEntry>::deserialize(deserializer)?;
+
+    if cycles.is_empty() {
+        return Err(D::Error::custom("cycles: the schedule holds no cycle"));
+    }
+
+    check_entries(&cycles).map_err(D::Error::custom)?;
+    Ok(cycles)
+}
+
+/// Refuses a group among `entries`, nested or not, that would flatten
+/// into no cycle.
+fn check_entries(entries: &[TimerEntry]) -> Result<(), &'static str> {
+    for entry in entries {
+        let TimerEntry::Group(group) = entry else {
+            continue;
+        };
+
+        if group.repeat == 0 {
+            return Err("cycles: a group must repeat at least once");
+        }
+
+        if group.cycles.is_empty() {
+            return Err("cycles: a group must hold at least one cycle");
+        }
+
+        check_entries(&group.cycles)?;
+    }
+
+    Ok(())
+}
+
+/// Renders `entry` as an inline TOML table.
+fn render_entry(entry: &TimerEntry) -> String {
+    let TimerCycle {
+        name,
+        duration,
+        auto_advance,
+        overtime,
+        count_up,
+        ratio,
+        max_duration,
+        iterations: _,
+    } = match entry {
+        TimerEntry::Cycle(cycle) => cycle,
+        TimerEntry::Group(TimerGroup { repeat, cycles }) => {
+            let cycles: Vec<_> = cycles.iter().map(render_entry).collect();
+            return format!("{{ repeat = {repeat}, cycles = [{}] }}", cycles.join(", "));
+        }
+    };
+
+    // NOTE: a cycle without a duration counts up, which is the shorter
+    // way to write one.
+    let mut cycle = format!("name = \"{name}\"");
+
+    if !count_up {
+        cycle.push_str(&render_duration("duration", *duration));
+    }
+
+    if !auto_advance {
+        cycle.push_str(", auto-advance = false");
+    }
+
+    if *overtime {
+        cycle.push_str(", overtime = true");
+    }
+
+    if let Some(ratio) = ratio {
+        cycle.push_str(&format!(", ratio = {}", f64::from(*ratio)));
+    }
+
+    if let Some(max) = max_duration {
+        cycle.push_str(&render_duration("max-duration", *max));
+    }
+
+    format!("{{ {cycle} }}")
+}
+
+/// Renders the `key` of a cycle lasting `duration` milliseconds, in
+/// seconds unless that would round some of it away.
+fn render_duration(key: &str, duration: usize) -> String {
+    if duration.is_multiple_of(1000) {
+        format!(", {key} = {}", duration / 1000)
+    } else {
+        format!(", {key}-ms = {duration}")
+    }
+}
@@ -47,7 +47,7 @@ use serde::Serialize;
 
 use crate::{
     cli::config::{AccountConfig, CONFIG_SAMPLE_URL, Config, SocketConfig, TcpConfig},
-    timer::{TimerCycle, TimerPrecision},
+    timer::{TimerCycle, TimerEntry, TimerJump, TimerPrecision},
 };
 
 /// Configure a timer account.
@@ -116,7 +116,7 @@ impl ConfigureCommand {
     }
 }
 
-/// The cycle presets the wizard offers, the three documented in the
+/// The cycle presets the wizard offers, the four documented in the
 /// sample configuration.
 ///
 /// A timer that follows none of them is written by hand: prompting for
@@ -131,14 +131,18 @@ enum TimerPreset {
     FiftyTwoSeventeen,
     /// 112 minutes of work, 26 minutes of rest.
     HundredTwelveTwentySix,
+    /// Work counting up until ended, then a fifth of it in rest,
+    /// between 5 and 30 minutes.
+    Flowtime,
 }
 
 impl TimerPreset {
     /// Every preset, in the order the prompt lists them.
-    const ALL: [Self; 3] = [
+    const ALL: [Self; 4] = [
         Self::Pomodoro,
         Self::FiftyTwoSeventeen,
         Self::HundredTwelveTwentySix,
+        Self::Flowtime,
     ];
 
     /// The account this preset generates.
@@ -154,6 +158,10 @@ impl TimerPreset {
             cycles: self.cycles(),
             cycles_count: None,
             precision: TimerPrecision::default(),
+            clock_jump: TimerJump::default(),
+            state: None,
+            history: None,
+            goal: None,
             hooks: HashMap::new(),
         }
     }
@@ -164,27 +172,40 @@ impl TimerPreset {
             Self::Pomodoro => "pomodoro",
             Self::FiftyTwoSeventeen => "52-17",
             Self::HundredTwelveTwentySix => "112-26",
+            Self::Flowtime => "flowtime",
         }
     }
 
     /// The ordered cycles this preset runs through.
-    fn cycles(self) -> Vec<TimerCycle> {
-        match self {
+    fn cycles(self) -> Vec<TimerEntry> {
+        let cycles = match self {
             Self::Pomodoro => vec![
-                TimerCycle::new("Work", 1500),
-                TimerCycle::new("Rest", 300),
-                TimerCycle::new("Work", 1500),
-                TimerCycle::new("Rest", 300),
-                TimerCycle::new("Work", 1500),
-                TimerCycle::new("Long rest", 1800),
+                TimerCycle::new("Work", 1_500_000),
+                TimerCycle::new("Rest", 300_000),
+                TimerCycle::new("Work", 1_500_000),
+                TimerCycle::new("Rest", 300_000),
+                TimerCycle::new("Work", 1_500_000),
+                TimerCycle::new("Long rest", 1_800_000),
             ],
             Self::FiftyTwoSeventeen => {
-                vec![TimerCycle::new("Work", 3120), TimerCycle::new("Rest", 1020)]
+                vec![
+                    TimerCycle::new("Work", 3_120_000),
+                    TimerCycle::new("Rest", 1_020_000),
+                ]
             }
             Self::HundredTwelveTwentySix => {
-                vec![TimerCycle::new("Work", 6720), TimerCycle::new("Rest", 1560)]
+                vec![
+                    TimerCycle::new("Work", 6_720_000),
+                    TimerCycle::new("Rest", 1_560_000),
+                ]
             }
-        }
+            Self::Flowtime => vec![
+                TimerCycle::count_up("Work"),
+                TimerCycle::new("Rest", 300_000).ratio(0.2, Some(1_800_000)),
+            ],
+        };
+
+        cycles.into_iter().map(TimerEntry::from).collect()
     }
 }
 
@@ -194,6 +215,7 @@ impl fmt::Display for TimerPreset {
             Self::Pomodoro => write!(f, "Pomodoro: 3 x (25 min of work, 5 of rest), 30 of rest"),
             Self::FiftyTwoSeventeen => write!(f, "52/17: 52 min of work, 17 of rest"),
             Self::HundredTwelveTwentySix => write!(f, "112/26: 112 min of work, 26 of rest"),
+            Self::Flowtime => write!(f, "Flowtime: open-ended work, a fifth of it in rest"),
         }
     }
 }
@@ -424,6 +446,20 @@ mod tests {
         assert_eq!(document.lines().count(), 10);
     }
 
+    #[test]
+    fn a_generated_flowtime_account_parses_back() {
+        let document = TimerPreset::Flowtime.account(true).render("flowtime");
+        let config: Config = toml::from_str(&document).expect("parse the generated config");
+
+        assert_eq!(
+            config.accounts["flowtime"].cycles,
+            TimerPreset::Flowtime.cycles()
+        );
+        assert!(document.contains(
+            "  { name = \"Rest\", duration = 300, ratio = 0.2, max-duration = 1800 },\n"
+        ));
+    }
+
     #[test]
     fn an_appended_account_keeps_the_existing_one() {
         let path = config_path();
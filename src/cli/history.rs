@@ -0,0 +1,405 @@
+//! Commands reading the history a server records.
+//!
+//! One module per command, each reading the records the server appended
+//! to the account history rather than asking the server, so they work
+//! whether it runs or not. The calendar they share sits here: the
+//! server records Unix epoch milliseconds, and a day only means
+//! something at an offset from UTC, which [`HistoryRangeArg`] carries
+//! along the days it selects.
+
+pub mod export;
+pub mod report;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use anyhow::{Result, bail};
+use clap::Parser;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{
+    cli::account::Account,
+    server::{clock::SystemClock, clock::TimerClock, history::TimerRecord},
+};
+
+/// The milliseconds of a day.
+const DAY: i64 = 86_400_000;
+
+/// A calendar day, in the proleptic Gregorian calendar.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    /// The year.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u32,
+    /// The day of the month, from 1.
+    pub day: u32,
+}
+
+impl Date {
+    /// The day that many days after 1970-01-01, or before when
+    /// negative.
+    pub fn from_days(days: i64) -> Self {
+        // NOTE: Howard Hinnant's `civil_from_days`, counting eras of
+        // 400 years from 0000-03-01 so the leap day ends each year.
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let doe = days.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+        Self {
+            year: (yoe + era * 400 + i64::from(month <= 2)) as i32,
+            month: month as u32,
+            day: (doy - (153 * mp + 2) / 5 + 1) as u32,
+        }
+    }
+
+    /// How many days this day is after 1970-01-01, negative before.
+    pub fn days(self) -> i64 {
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = if month > 2 { month - 3 } else { month + 9 };
+        let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146_097 + doe - 719_468
+    }
+
+    /// The day of the week, from 0 for Monday to 6 for Sunday.
+    pub fn weekday(self) -> u32 {
+        (self.days() + 3).rem_euclid(7) as u32
+    }
+
+    /// The day after this one.
+    pub fn next(self) -> Self {
+        Self::from_days(self.days() + 1)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid date `{s}`, expected YYYY-MM-DD");
+
+        let mut parts = s.splitn(3, '-');
+        let mut part = || parts.next().and_then(|part| part.parse().ok());
+
+        let (Some(year), Some(month), Some(day)) = (part(), part(), part()) else {
+            return Err(invalid());
+        };
+
+        let date = Self {
+            year: year as i32,
+            month,
+            day,
+        };
+
+        // NOTE: a day past the end of its month, such as 2026-02-30,
+        // lands in the next one once counted, which is how it is told.
+        if !(1..=12).contains(&month) || day == 0 || Self::from_days(date.days()) != date {
+            return Err(invalid());
+        }
+
+        Ok(date)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Date {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        "Date".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "type": "string", "format": "date" })
+    }
+}
+
+/// A time of day, to the second.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Time {
+    /// The hour, from 0 to 23.
+    pub hour: u32,
+    /// The minute, from 0 to 59.
+    pub minute: u32,
+    /// The second, from 0 to 59.
+    pub second: u32,
+}
+
+impl Time {
+    /// The milliseconds elapsed since midnight at this time of day.
+    pub fn ms(self) -> i64 {
+        (self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64) * 1000
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+impl FromStr for Time {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid time `{s}`, expected HH:MM or HH:MM:SS");
+
+        let mut parts = s.splitn(3, ':');
+        let mut part = || parts.next().map(str::parse::<u32>);
+
+        let (Some(Ok(hour)), Some(Ok(minute))) = (part(), part()) else {
+            return Err(invalid());
+        };
+
+        let second = match part() {
+            None => 0,
+            Some(Ok(second)) => second,
+            Some(Err(_)) => return Err(invalid()),
+        };
+
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            hour,
+            minute,
+            second,
+        })
+    }
+}
+
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// An offset from UTC, in minutes, which days are counted at.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct UtcOffset(pub i64);
+
+impl UtcOffset {
+    /// The day `ms`, in Unix epoch milliseconds, falls on.
+    pub fn date(self, ms: u64) -> Date {
+        Date::from_days((ms as i64 + self.0 * 60_000).div_euclid(DAY))
+    }
+
+    /// The time of day `ms`, in Unix epoch milliseconds, falls at.
+    pub fn time(self, ms: u64) -> Time {
+        let secs = (ms as i64 + self.0 * 60_000).rem_euclid(DAY) / 1000;
+
+        Time {
+            hour: (secs / 3600) as u32,
+            minute: (secs % 3600 / 60) as u32,
+            second: (secs % 60) as u32,
+        }
+    }
+
+    /// The Unix epoch milliseconds at which `date` begins, zero for a
+    /// day beginning before the epoch.
+    pub fn start(self, date: Date) -> u64 {
+        (date.days() * DAY - self.0 * 60_000).max(0) as u64
+    }
+}
+
+impl fmt::Display for UtcOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { '-' } else { '+' };
+        let minutes = self.0.abs();
+        write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+impl FromStr for UtcOffset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid UTC offset `{s}`, expected +HH:MM or -HH:MM");
+
+        if s == "Z" {
+            return Ok(Self(0));
+        }
+
+        let (sign, rest) = match s.split_at_checked(1) {
+            Some(("+", rest)) => (1, rest),
+            Some(("-", rest)) => (-1, rest),
+            _ => return Err(invalid()),
+        };
+
+        let Some((hours, minutes)) = rest.split_once(':') else {
+            return Err(invalid());
+        };
+
+        let (Ok(hours), Ok(minutes)) = (hours.parse::<i64>(), minutes.parse::<i64>()) else {
+            return Err(invalid());
+        };
+
+        if hours > 14 || minutes > 59 {
+            return Err(invalid());
+        }
+
+        Ok(Self(sign * (hours * 60 + minutes)))
+    }
+}
+
+impl Serialize for UtcOffset {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for UtcOffset {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The range of days the history commands share.
+#[derive(Debug, Parser)]
+pub struct HistoryRangeArg {
+    /// Leave out the cycles that began before that day.
+    ///
+    /// Given as YYYY-MM-DD. The whole history is read when absent.
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<Date>,
+    /// Leave out the cycles that began after that day.
+    ///
+    /// Given as YYYY-MM-DD, the day itself included.
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<Date>,
+    /// The offset from UTC the days are counted at.
+    ///
+    /// The server records instants, so this is what decides which day
+    /// a cycle belongs to, and when a day, a week or a month begins.
+    #[arg(long, value_name = "OFFSET", env = "COMODORO_UTC_OFFSET")]
+    #[arg(default_value = "+00:00", allow_hyphen_values = true)]
+    pub utc_offset: UtcOffset,
+}
+
+impl HistoryRangeArg {
+    /// Reads the records of the account history that began within the
+    /// range, oldest first.
+    pub fn read(&self, account: &Account) -> Result<Vec<TimerRecord>> {
+        let Some(history) = &account.history else {
+            bail!("No history recorded for this account, set `history.path` to record one");
+        };
+
+        let mut records = history.read()?;
+        records.retain(|record| self.contains(record));
+        Ok(records)
+    }
+
+    /// Whether `record` began within the range.
+    pub fn contains(&self, record: &TimerRecord) -> bool {
+        let date = self.utc_offset.date(record.began_at_ms);
+        let after = self.since.is_none_or(|since| date >= since);
+        let before = self.until.is_none_or(|until| date <= until);
+        after && before
+    }
+
+    /// The last day of the range: today, or the end of the range when
+    /// it ends earlier.
+    pub fn last(&self) -> Date {
+        let today = self.utc_offset.date(SystemClock.now());
+        self.until.map_or(today, |until| until.min(today))
+    }
+}
+
+impl fmt::Display for HistoryRangeArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let since = self.since.as_ref().map(ToString::to_string);
+        let until = self.until.as_ref().map(ToString::to_string);
+
+        match (since, until) {
+            (None, None) => write!(f, "whole history"),
+            (Some(since), None) => write!(f, "since {since}"),
+            (None, Some(until)) => write!(f, "until {until}"),
+            (Some(since), Some(until)) => write!(f, "{since} to {until}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::{Date, Time, UtcOffset};
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn days_count_from_the_epoch_both_ways() {
+        for (s, days) in [
+            ("1970-01-01", 0),
+            ("1969-12-31", -1),
+            ("2000-02-29", 11_016),
+            ("2026-10-18", 20_744),
+        ] {
+            assert_eq!(date(s).days(), days, "{s}");
+            assert_eq!(Date::from_days(days).to_string(), s);
+        }
+
+        assert_eq!(date("2026-10-18").weekday(), 6);
+        assert_eq!(date("2026-10-12").weekday(), 0);
+    }
+
+    #[test]
+    fn a_day_past_the_end_of_its_month_is_no_date() {
+        assert!("2026-02-29".parse::<Date>().is_err());
+        assert!("2026-13-01".parse::<Date>().is_err());
+        assert!("2026-10".parse::<Date>().is_err());
+        assert!("2024-02-29".parse::<Date>().is_ok());
+    }
+
+    #[test]
+    fn the_offset_decides_the_day_an_instant_falls_on() {
+        // 2026-10-18T23:30:00Z
+        let ms = 1_792_366_200_000;
+
+        assert_eq!(UtcOffset(0).date(ms), date("2026-10-18"));
+        assert_eq!("+02:00".parse(), Ok(UtcOffset(120)));
+        assert_eq!(UtcOffset(120).date(ms), date("2026-10-19"));
+        assert_eq!(UtcOffset(120).time(ms).to_string(), "01:30:00");
+        assert_eq!("-05:30".parse(), Ok(UtcOffset(-330)));
+        assert_eq!(UtcOffset(-330).to_string(), "-05:30");
+        assert_eq!(UtcOffset(120).start(date("2026-10-19")), 1_792_360_800_000);
+        assert_eq!("04:30".parse::<Time>().map(Time::ms), Ok(16_200_000));
+        assert!("24:00".parse::<Time>().is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! Command exporting the history.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{self, Write},
+    iter,
+};
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use pimalaya_cli::printer::Printer;
+use serde::{Serialize, Serializer};
+
+use crate::{
+    cli::{
+        account::Account,
+        history::{HistoryRangeArg, UtcOffset},
+    },
+    server::history::{TimerEnd, TimerRecord},
+};
+
+/// Export the history.
+///
+/// This command reads the cycles the server recorded and prints them in
+/// a format other tools read: calendar events, spreadsheet rows, or
+/// timewarrior intervals. The account needs a `history` table for the
+/// server to record anything.
+#[derive(Debug, Parser)]
+pub struct HistoryExportCommand {
+    /// The format the cycles are exported in.
+    #[arg(long, short, value_name = "FORMAT", value_enum)]
+    pub format: ExportFormat,
+    /// The days the export covers.
+    #[command(flatten)]
+    pub range: HistoryRangeArg,
+}
+
+impl HistoryExportCommand {
+    /// Prints the cycles of the account history in the given format.
+    pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
+        printer.out(Export {
+            format: self.format,
+            offset: self.range.utc_offset,
+            records: self.range.read(account)?,
+        })
+    }
+}
+
+/// A format the history exports in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    /// An iCalendar document, one event per cycle.
+    Ics,
+    /// Comma-separated values, one row per cycle below a header.
+    Csv,
+    /// Timewarrior interval lines, tagged with the cycle name.
+    Timew,
+}
+
+/// The cycles of a history, in the format they export in.
+///
+/// Under `--json` the printer takes the records themselves, as the
+/// history holds them, whatever the format.
+pub struct Export {
+    /// The format the records render in.
+    pub format: ExportFormat,
+    /// The offset from UTC the spreadsheet rows show their times at.
+    pub offset: UtcOffset,
+    /// The records to export, oldest first.
+    pub records: Vec<TimerRecord>,
+}
+
+impl fmt::Display for Export {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.format {
+            ExportFormat::Ics => self.fmt_ics(f),
+            ExportFormat::Csv => self.fmt_csv(f),
+            ExportFormat::Timew => self.fmt_timew(f),
+        }
+    }
+}
+
+impl Serialize for Export {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.records.serialize(serializer)
+    }
+}
+
+impl Export {
+    /// Renders an iCalendar document (RFC 5545), its times in UTC.
+    fn fmt_ics(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ics_line(f, "BEGIN:VCALENDAR")?;
+        ics_line(f, "VERSION:2.0")?;
+        ics_line(f, "PRODID:-//pimalaya//comodoro//EN")?;
+
+        for record in &self.records {
+            let began = UtcStamp(record.began_at_ms);
+            let ended = UtcStamp(record.ended_at_ms);
+
+            ics_line(f, "BEGIN:VEVENT")?;
+            let uid = format!("UID:{}-{}@comodoro", record.began_at_ms, record.ended_at_ms);
+            ics_line(f, &uid)?;
+            ics_line(f, &format!("DTSTAMP:{ended}"))?;
+            ics_line(f, &format!("DTSTART:{began}"))?;
+            ics_line(f, &format!("DTEND:{ended}"))?;
+            match &record.task {
+                Some(task) => {
+                    let summary = format!("{}: {task}", record.cycle);
+                    ics_line(f, &format!("SUMMARY:{}", IcsText(&summary)))?;
+                }
+                None => ics_line(f, &format!("SUMMARY:{}", IcsText(&record.cycle)))?,
+            }
+
+            if !record.tags.is_empty() {
+                let tags: Vec<_> = record
+                    .tags
+                    .iter()
+                    .map(|tag| IcsText(tag).to_string())
+                    .collect();
+                ics_line(f, &format!("CATEGORIES:{}", tags.join(",")))?;
+            }
+
+            ics_line(f, &format!("DESCRIPTION:{}", IcsText(&describe(record))))?;
+            ics_line(f, "END:VEVENT")?;
+        }
+
+        ics_line(f, "END:VCALENDAR")
+    }
+
+    /// Renders a header then one row per record, its times in RFC 3339
+    /// at the offset.
+    fn fmt_csv(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "cycle,began_at,ended_at,planned_ms,actual_ms,pauses,paused_ms,end,task,tags"
+        )?;
+
+        for record in &self.records {
+            let offset = self.offset;
+            let began = offset.date(record.began_at_ms);
+            let began_at = offset.time(record.began_at_ms);
+            let ended = offset.date(record.ended_at_ms);
+            let ended_at = offset.time(record.ended_at_ms);
+
+            writeln!(
+                f,
+                "{},{began}T{began_at}{offset},{ended}T{ended_at}{offset},{},{},{},{},{},{},{}",
+                CsvField(&record.cycle),
+                record
+                    .planned_ms
+                    .map(|ms| ms.to_string())
+                    .unwrap_or_default(),
+                record.actual_ms,
+                record.pauses,
+                record.paused_ms,
+                end_name(record.end),
+                CsvField(record.task.as_deref().unwrap_or_default()),
+                CsvField(&record.tags.join(",")),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders one interval line per record, as timewarrior keeps them
+    /// in its data files, tagged with the cycle name.
+    fn fmt_timew(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for record in &self.records {
+            let began = UtcStamp(record.began_at_ms);
+            let ended = UtcStamp(record.ended_at_ms);
+            write!(f, "inc {began} - {ended} #")?;
+
+            for tag in iter::once(&record.cycle).chain(&record.tags) {
+                write!(f, " {}", TimewText(tag))?;
+            }
+
+            match &record.task {
+                Some(task) => writeln!(f, " # \"{}\"", task.replace('"', "\\\""))?,
+                None => writeln!(f)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a line of iCalendar.
+///
+/// iCalendar ends its lines with CRLF, and folds the ones longer than
+/// 75 octets, which only a long cycle name reaches.
+fn ics_line(f: &mut fmt::Formatter<'_>, line: &str) -> fmt::Result {
+    let mut octets = 0;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > 75 {
+            f.write_str("\r\n ")?;
+            octets = 1;
+        }
+
+        f.write_char(c)?;
+        octets += c.len_utf8();
+    }
+
+    f.write_str("\r\n")
+}
+
+/// What an event says about its cycle beyond its name and times.
+fn describe(record: &TimerRecord) -> String {
+    let mut description = format!("{}, ran {}s", end_name(record.end), record.actual_ms / 1000);
+
+    if let Some(planned) = record.planned_ms {
+        description += &format!(" of {}s", planned / 1000);
+    }
+
+    if record.pauses > 0 {
+        description += &format!(
+            ", paused {} time(s) for {}s",
+            record.pauses,
+            record.paused_ms / 1000
+        );
+    }
+
+    description
+}
+
+/// The name the history gives `end`.
+fn end_name(end: TimerEnd) -> &'static str {
+    match end {
+        TimerEnd::Completed => "completed",
+        TimerEnd::Skipped => "skipped",
+        TimerEnd::Stopped => "stopped",
+        TimerEnd::Moved => "moved",
+    }
+}
+
+/// Unix epoch milliseconds in the basic format of ISO 8601, in UTC,
+/// which both iCalendar and timewarrior read.
+struct UtcStamp(u64);
+
+impl fmt::Display for UtcStamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = UtcOffset(0).date(self.0);
+        let time = UtcOffset(0).time(self.0);
+
+        write!(
+            f,
+            "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+            date.year, date.month, date.day, time.hour, time.minute, time.second,
+        )
+    }
+}
+
+/// A text value of iCalendar, its special characters escaped.
+struct IcsText<'a>(&'a str);
+
+impl fmt::Display for IcsText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '\\' | ';' | ',' => write!(f, "\\{c}")?,
+                '\n' => f.write_str("\\n")?,
+                c => f.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A tag of timewarrior, quoted when it holds a space, since it splits
+/// its tags on them, or a quote, like it does itself.
+struct TimewText<'a>(&'a str);
+
+impl fmt::Display for TimewText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.0.contains([' ', '"']) {
+            return f.write_str(self.0);
+        }
+
+        write!(f, "\"{}\"", self.0.replace('"', "\\\""))
+    }
+}
+
+/// A field of CSV, quoted when it holds a character the format uses.
+struct CsvField<'a>(&'a str);
+
+impl fmt::Display for CsvField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.0.contains([',', '"', '\n', '\r']) {
+            return f.write_str(self.0);
+        }
+
+        write!(f, "\"{}\"", self.0.replace('"', "\"\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        string::{String, ToString},
+        vec,
+    };
+
+    use crate::{
+        cli::history::UtcOffset,
+        server::history::{TimerEnd, TimerRecord},
+    };
+
+    use super::{Export, ExportFormat};
+
+    /// A Work cycle from 2026-10-12T09:00:00Z to 09:27:00Z, paused 2
+    /// minutes.
+    fn export(format: ExportFormat, cycle: &str) -> String {
+        export_labeled(format, cycle, None, &[])
+    }
+
+    fn export_labeled(
+        format: ExportFormat,
+        cycle: &str,
+        task: Option<&str>,
+        tags: &[&str],
+    ) -> String {
+        let record = TimerRecord {
+            cycle: cycle.to_string(),
+            began_at_ms: 1_791_795_600_000,
+            ended_at_ms: 1_791_797_220_000,
+            planned_ms: Some(1_500_000),
+            actual_ms: 1_500_000,
+            pauses: 1,
+            paused_ms: 120_000,
+            end: TimerEnd::Completed,
+            task: task.map(ToString::to_string),
+            tags: tags.iter().map(ToString::to_string).collect(),
+            interruptions: vec![],
+        };
+
+        let export = Export {
+            format,
+            offset: UtcOffset(120),
+            records: vec![record],
+        };
+
+        export.to_string()
+    }
+
+    #[test]
+    fn a_cycle_exports_as_a_calendar_event() {
+        let ics = export(ExportFormat::Ics, "Work, deep");
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("\r\nDTSTART:20261012T090000Z\r\nDTEND:20261012T092700Z\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Work\\, deep\r\n"));
+        assert!(
+            ics.contains(
+                "DESCRIPTION:completed\\, ran 1500s of 1500s\\, paused 1 time(s) for 120s"
+            )
+        );
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+
+        let long = export(ExportFormat::Ics, &"a".repeat(80));
+        let summary = long
+            .lines()
+            .find(|line| line.starts_with("SUMMARY:"))
+            .unwrap();
+        assert_eq!(summary.trim_end().len(), 75);
+    }
+
+    #[test]
+    fn a_cycle_exports_as_a_spreadsheet_row() {
+        let csv = export(ExportFormat::Csv, "Work \"deep\"");
+
+        assert_eq!(
+            csv,
+            "cycle,began_at,ended_at,planned_ms,actual_ms,pauses,paused_ms,end,task,tags\n\
+             \"Work \"\"deep\"\"\",2026-10-12T11:00:00+02:00,2026-10-12T11:27:00+02:00,1500000,1500000,1,120000,completed,,\n"
+        );
+    }
+
+    #[test]
+    fn a_cycle_exports_as_a_timewarrior_interval() {
+        let timew = export(ExportFormat::Timew, "Work");
+        assert_eq!(timew, "inc 20261012T090000Z - 20261012T092700Z # Work\n");
+
+        let timew = export(ExportFormat::Timew, "Long rest");
+        assert_eq!(
+            timew,
+            "inc 20261012T090000Z - 20261012T092700Z # \"Long rest\"\n"
+        );
+    }
+
+    #[test]
+    fn a_labeled_cycle_exports_its_task_and_tags() {
+        let task = Some("review \"PR\" 42");
+        let tags = ["oss", "code review"];
+
+        let ics = export_labeled(ExportFormat::Ics, "Work", task, &tags);
+        assert!(ics.contains("\r\nSUMMARY:Work: review \"PR\" 42\r\n"));
+        assert!(ics.contains("\r\nCATEGORIES:oss,code review\r\n"));
+
+        let csv = export_labeled(ExportFormat::Csv, "Work", task, &tags);
+        assert!(csv.ends_with(",completed,\"review \"\"PR\"\" 42\",\"oss,code review\"\n"));
+
+        let timew = export_labeled(ExportFormat::Timew, "Work", task, &tags);
+        assert_eq!(
+            timew,
+            "inc 20261012T090000Z - 20261012T092700Z # Work oss \"code review\" # \"review \\\"PR\\\" 42\"\n"
+        );
+    }
+}
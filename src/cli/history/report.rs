@@ -0,0 +1,572 @@
+//! Command reporting the history.
+
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use pimalaya_cli::printer::Printer;
+use serde::Serialize;
+
+use crate::{
+    cli::{
+        account::Account,
+        history::{Date, HistoryRangeArg, UtcOffset},
+    },
+    server::history::{TimerEnd, TimerRecord},
+    timer::TimerInterruptionKind,
+};
+
+/// Report the history.
+///
+/// This command reads the cycles the server recorded and prints, for
+/// every day, week or month, how long each cycle ran, how many work
+/// cycles completed, how long the pauses lasted and how often the
+/// cycles were interrupted, then the streak of days with a work cycle
+/// completed. The account needs a `history` table for the server to
+/// record anything.
+#[derive(Debug, Parser)]
+pub struct HistoryReportCommand {
+    /// The periods the cycles are totalled over.
+    #[arg(long, short, value_name = "PERIOD", value_enum, default_value_t)]
+    pub by: ReportPeriod,
+    /// The cycles that count as work.
+    ///
+    /// Defaults to the first cycle of the schedule, which is the work
+    /// of every preset. Can be given several times.
+    #[arg(long = "work", short, value_name = "CYCLE")]
+    pub work: Vec<String>,
+    /// The days the report covers.
+    #[command(flatten)]
+    pub range: HistoryRangeArg,
+}
+
+impl HistoryReportCommand {
+    /// Prints the report of the account history.
+    pub fn execute(self, printer: &mut impl Printer, account: &Account) -> Result<()> {
+        let records = self.range.read(account)?;
+
+        let work = if self.work.is_empty() {
+            let first = account.schedule.cycles.first();
+            first.map(|cycle| cycle.name.clone()).into_iter().collect()
+        } else {
+            self.work
+        };
+
+        let offset = self.range.utc_offset;
+        let report = Report::new(&records, self.by, work, offset, self.range.last());
+        printer.out(report)
+    }
+}
+
+/// How long the periods of a report last.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportPeriod {
+    /// One period per day.
+    #[default]
+    Day,
+    /// One period per week, from Monday to Sunday.
+    Week,
+    /// One period per calendar month.
+    Month,
+}
+
+impl ReportPeriod {
+    /// The first day of the period `date` falls in.
+    fn first(self, date: Date) -> Date {
+        match self {
+            Self::Day => date,
+            Self::Week => Date::from_days(date.days() - i64::from(date.weekday())),
+            Self::Month => Date { day: 1, ..date },
+        }
+    }
+
+    /// The last day of the period beginning on `first`.
+    fn last(self, first: Date) -> Date {
+        match self {
+            Self::Day => first,
+            Self::Week => Date::from_days(first.days() + 6),
+            Self::Month => {
+                let next = match first.month {
+                    12 => Date {
+                        year: first.year + 1,
+                        month: 1,
+                        day: 1,
+                    },
+                    month => Date {
+                        month: month + 1,
+                        ..first
+                    },
+                };
+
+                Date::from_days(next.days() - 1)
+            }
+        }
+    }
+}
+
+/// What the history amounts to, as the printer takes it.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// How long each period lasts.
+    pub by: ReportPeriod,
+    /// The names of the cycles counted as work.
+    pub work: Vec<String>,
+    /// The periods with a cycle recorded, oldest first.
+    pub periods: Vec<ReportEntry>,
+    /// The totals of every period together.
+    pub total: ReportTotals,
+    /// The runs of consecutive days with a work cycle completed.
+    pub streak: ReportStreak,
+}
+
+/// The totals of one period.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize)]
+pub struct ReportEntry {
+    /// The first day of the period.
+    pub first: Date,
+    /// The last day of the period, included.
+    pub last: Date,
+    /// What the cycles that began during the period amount to.
+    #[serde(flatten)]
+    pub totals: ReportTotals,
+}
+
+/// What a set of cycles amounts to.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Serialize)]
+pub struct ReportTotals {
+    /// The totals per cycle name, in the order they first ran.
+    pub cycles: Vec<ReportCycle>,
+    /// How many work cycles ran out, rather than being skipped, stopped
+    /// or moved away from.
+    pub completed_work: usize,
+    /// How many times the cycles were paused.
+    pub pauses: usize,
+    /// The milliseconds the cycles spent paused.
+    pub paused_ms: usize,
+    /// The milliseconds a pause lasted on average, absent without any.
+    pub average_pause_ms: Option<usize>,
+    /// How many times the cycles were interrupted from within.
+    pub internal_interruptions: usize,
+    /// How many times something else interrupted the cycles.
+    pub external_interruptions: usize,
+}
+
+/// The totals of the cycles sharing a name.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Serialize)]
+pub struct ReportCycle {
+    /// The name of the cycles.
+    pub name: String,
+    /// How many of them ran, however they ended.
+    pub count: usize,
+    /// How many of them ran out.
+    pub completed: usize,
+    /// The milliseconds they ran, their pauses left out.
+    pub actual_ms: usize,
+}
+
+/// The runs of consecutive days with a work cycle completed.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Serialize)]
+pub struct ReportStreak {
+    /// The days of the run reaching the last day of the report, or the
+    /// day before, since the last day may still have work to come.
+    pub current: usize,
+    /// The days of the longest run.
+    pub longest: usize,
+}
+
+impl Report {
+    /// Totals `records` per `by` period, counting the cycles named in
+    /// `work` as work, and the days at `offset` from UTC.
+    ///
+    /// A cycle counts in the period it began in, even when it ended in
+    /// the next one. `last` is the day the current streak runs up to.
+    pub fn new(
+        records: &[TimerRecord],
+        by: ReportPeriod,
+        work: Vec<String>,
+        offset: UtcOffset,
+        last: Date,
+    ) -> Self {
+        let mut periods: Vec<ReportEntry> = Vec::new();
+        let mut total = ReportTotals::default();
+        let mut days = BTreeSet::new();
+
+        for record in records {
+            let date = offset.date(record.began_at_ms);
+            let first = by.first(date);
+            let is_work = work.contains(&record.cycle);
+
+            // NOTE: the history is appended in the order cycles end,
+            // which is the order they began, so a period once left is
+            // never met again, bar a clock set back.
+            let entry = match periods.iter_mut().rposition(|entry| entry.first == first) {
+                Some(index) => &mut periods[index],
+                None => {
+                    periods.push(ReportEntry {
+                        first,
+                        last: by.last(first),
+                        totals: ReportTotals::default(),
+                    });
+                    periods.last_mut().unwrap()
+                }
+            };
+
+            entry.totals.add(record, is_work);
+            total.add(record, is_work);
+
+            if is_work && record.end == TimerEnd::Completed {
+                days.insert(date.days());
+            }
+        }
+
+        periods.sort_by_key(|entry| entry.first);
+
+        Self {
+            by,
+            work,
+            periods,
+            total,
+            streak: ReportStreak::new(&days, last),
+        }
+    }
+}
+
+impl ReportTotals {
+    fn add(&mut self, record: &TimerRecord, is_work: bool) {
+        let completed = record.end == TimerEnd::Completed;
+
+        let cycle = match self.cycles.iter_mut().position(|c| c.name == record.cycle) {
+            Some(index) => &mut self.cycles[index],
+            None => {
+                self.cycles.push(ReportCycle {
+                    name: record.cycle.clone(),
+                    count: 0,
+                    completed: 0,
+                    actual_ms: 0,
+                });
+                self.cycles.last_mut().unwrap()
+            }
+        };
+
+        cycle.count += 1;
+        cycle.completed += usize::from(completed);
+        cycle.actual_ms += record.actual_ms;
+
+        self.completed_work += usize::from(is_work && completed);
+        self.pauses += record.pauses;
+        self.paused_ms += record.paused_ms;
+        self.average_pause_ms = self.paused_ms.checked_div(self.pauses);
+
+        for interruption in &record.interruptions {
+            match interruption.kind {
+                TimerInterruptionKind::Internal => self.internal_interruptions += 1,
+                TimerInterruptionKind::External => self.external_interruptions += 1,
+            }
+        }
+    }
+}
+
+impl ReportStreak {
+    /// The streaks of `days`, counted from the epoch, up to `last`.
+    fn new(days: &BTreeSet<i64>, last: Date) -> Self {
+        let mut longest = 0;
+        let mut run = 0;
+        let mut previous = None;
+
+        for day in days {
+            run = if previous == Some(day - 1) {
+                run + 1
+            } else {
+                1
+            };
+            longest = longest.max(run);
+            previous = Some(*day);
+        }
+
+        let mut day = last.days();
+
+        if !days.contains(&day) {
+            day -= 1;
+        }
+
+        let mut current = 0;
+
+        while days.contains(&day) {
+            current += 1;
+            day -= 1;
+        }
+
+        Self { current, longest }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.periods.is_empty() {
+            return writeln!(f, "No cycle recorded");
+        }
+
+        let width = self
+            .total
+            .cycles
+            .iter()
+            .map(|cycle| cycle.name.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        for entry in &self.periods {
+            match self.by {
+                ReportPeriod::Day => writeln!(f, "{}", entry.first)?,
+                ReportPeriod::Week => writeln!(f, "Week of {}", entry.first)?,
+                ReportPeriod::Month => {
+                    writeln!(f, "{:04}-{:02}", entry.first.year, entry.first.month)?
+                }
+            }
+
+            entry.totals.fmt_lines(f, width)?;
+            writeln!(f)?;
+        }
+
+        writeln!(f, "Total")?;
+        self.total.fmt_lines(f, width)?;
+        writeln!(f)?;
+
+        let ReportStreak { current, longest } = self.streak;
+        writeln!(f, "Streak: {current} day(s), longest {longest} day(s)")
+    }
+}
+
+impl ReportTotals {
+    fn fmt_lines(&self, f: &mut fmt::Formatter<'_>, width: usize) -> fmt::Result {
+        for cycle in &self.cycles {
+            writeln!(
+                f,
+                "  {:width$}  {} cycle(s), {}",
+                cycle.name,
+                cycle.count,
+                Duration(cycle.actual_ms),
+            )?;
+        }
+
+        write!(f, "  {} work cycle(s) completed", self.completed_work)?;
+
+        match self.average_pause_ms {
+            None => writeln!(f, ", no pause")?,
+            Some(average) => writeln!(
+                f,
+                ", {} pause(s) of {} on average",
+                self.pauses,
+                Duration(average),
+            )?,
+        }
+
+        let (internal, external) = (self.internal_interruptions, self.external_interruptions);
+
+        if internal + external > 0 {
+            writeln!(
+                f,
+                "  {} interruption(s), {internal} internal and {external} external",
+                internal + external,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Milliseconds as the report shows them, to the second.
+struct Duration(usize);
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0 / 1000;
+        let (hours, mins, secs) = (secs / 3600, secs % 3600 / 60, secs % 60);
+
+        let parts = [(hours, "h"), (mins, "min"), (secs, "s")];
+        let parts: Vec<_> = parts
+            .into_iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, unit)| n.to_string() + unit)
+            .collect();
+
+        if parts.is_empty() {
+            return write!(f, "0s");
+        }
+
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    use crate::{
+        cli::history::{Date, UtcOffset},
+        server::history::{TimerEnd, TimerRecord},
+        timer::{TimerInterruption, TimerInterruptionKind},
+    };
+
+    use super::{Report, ReportPeriod};
+
+    /// 2026-10-12T00:00:00Z, a Monday.
+    const MONDAY: u64 = 1_791_763_200_000;
+    const DAY: u64 = 86_400_000;
+
+    fn record(cycle: &str, began_at_ms: u64, actual_ms: usize, end: TimerEnd) -> TimerRecord {
+        TimerRecord {
+            cycle: cycle.to_string(),
+            began_at_ms,
+            ended_at_ms: began_at_ms + actual_ms as u64,
+            planned_ms: Some(1_500_000),
+            actual_ms,
+            pauses: 0,
+            paused_ms: 0,
+            end,
+            task: None,
+            tags: vec![],
+            interruptions: vec![],
+        }
+    }
+
+    fn records() -> Vec<TimerRecord> {
+        let mut paused = record(
+            "Work",
+            MONDAY + 9 * 3_600_000,
+            1_500_000,
+            TimerEnd::Completed,
+        );
+        paused.pauses = 2;
+        paused.paused_ms = 90_000;
+        paused.interruptions = vec![
+            TimerInterruption {
+                kind: TimerInterruptionKind::Internal,
+                note: None,
+            },
+            TimerInterruption {
+                kind: TimerInterruptionKind::External,
+                note: Some("call".to_string()),
+            },
+        ];
+
+        vec![
+            paused,
+            record(
+                "Rest",
+                MONDAY + 10 * 3_600_000,
+                300_000,
+                TimerEnd::Completed,
+            ),
+            record("Work", MONDAY + DAY, 600_000, TimerEnd::Skipped),
+            record("Work", MONDAY + 2 * DAY, 1_500_000, TimerEnd::Completed),
+            record("Work", MONDAY + 3 * DAY, 1_500_000, TimerEnd::Completed),
+            record("Work", MONDAY + 7 * DAY, 1_500_000, TimerEnd::Completed),
+        ]
+    }
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    /// The report of `records` at UTC, `Work` counted as work.
+    fn report(records: &[TimerRecord], by: ReportPeriod, last: &str) -> Report {
+        let work = vec!["Work".to_string()];
+        Report::new(records, by, work, UtcOffset(0), date(last))
+    }
+
+    #[test]
+    fn a_daily_report_totals_every_day_apart() {
+        let report = report(&records(), ReportPeriod::Day, "2026-10-19");
+
+        let firsts: Vec<_> = report.periods.iter().map(|entry| entry.first).collect();
+        let days = [
+            "2026-10-12",
+            "2026-10-13",
+            "2026-10-14",
+            "2026-10-15",
+            "2026-10-19",
+        ];
+        assert_eq!(firsts, days.map(date));
+
+        let monday = &report.periods[0].totals;
+        assert_eq!(monday.cycles.len(), 2);
+        assert_eq!(monday.cycles[0].actual_ms, 1_500_000);
+        assert_eq!(monday.completed_work, 1);
+        assert_eq!(monday.average_pause_ms, Some(45_000));
+        assert_eq!(monday.internal_interruptions, 1);
+        assert_eq!(monday.external_interruptions, 1);
+
+        assert_eq!(report.periods[1].totals.completed_work, 0);
+        assert_eq!(report.total.completed_work, 4);
+        assert_eq!(report.total.cycles[0].count, 5);
+        assert_eq!(report.total.cycles[0].actual_ms, 6_600_000);
+    }
+
+    #[test]
+    fn a_weekly_report_runs_from_monday_to_sunday() {
+        let report = report(&records(), ReportPeriod::Week, "2026-10-19");
+
+        assert_eq!(report.periods.len(), 2);
+        assert_eq!(report.periods[0].first, date("2026-10-12"));
+        assert_eq!(report.periods[0].last, date("2026-10-18"));
+        assert_eq!(report.periods[0].totals.completed_work, 3);
+        assert_eq!(report.periods[1].first, date("2026-10-19"));
+    }
+
+    #[test]
+    fn a_monthly_report_runs_to_the_end_of_the_month() {
+        let last = date("2026-10-19");
+        let report = Report::new(&records(), ReportPeriod::Month, vec![], UtcOffset(0), last);
+
+        assert_eq!(report.periods.len(), 1);
+        assert_eq!(report.periods[0].first, date("2026-10-01"));
+        assert_eq!(report.periods[0].last, date("2026-10-31"));
+        assert_eq!(report.total.completed_work, 0);
+    }
+
+    #[test]
+    fn a_streak_runs_over_the_days_with_completed_work() {
+        let streak = report(&records(), ReportPeriod::Day, "2026-10-19").streak;
+        assert_eq!(streak.longest, 2);
+        assert_eq!(streak.current, 1);
+
+        // NOTE: the last day may still have work to come, so a streak
+        // ending the day before still runs.
+        let streak = report(&records(), ReportPeriod::Day, "2026-10-20").streak;
+        assert_eq!(streak.current, 1);
+
+        let streak = report(&records(), ReportPeriod::Day, "2026-10-21").streak;
+        assert_eq!(streak.current, 0);
+    }
+
+    #[test]
+    fn the_offset_moves_a_cycle_to_the_day_it_began_there() {
+        let late = [record(
+            "Work",
+            MONDAY - 3_600_000,
+            1_500_000,
+            TimerEnd::Completed,
+        )];
+        let work = vec!["Work".to_string()];
+        let last = date("2026-10-12");
+
+        let report = Report::new(&late, ReportPeriod::Day, work.clone(), UtcOffset(0), last);
+        assert_eq!(report.periods[0].first, date("2026-10-11"));
+
+        let report = Report::new(&late, ReportPeriod::Day, work, UtcOffset(120), last);
+        assert_eq!(report.periods[0].first, date("2026-10-12"));
+    }
+}
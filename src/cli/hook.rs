@@ -13,7 +13,10 @@
 //! notification backend, so an account carrying one is refused as it
 //! loads, naming the cargo feature to rebuild with.
 
-use alloc::{format, string::String};
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 
 use std::process::Command;
 
@@ -32,18 +35,27 @@ impl TimerEvent {
     /// The configuration key holding the hook bound to this event.
     ///
     /// A cycle event is named after the kebab-case cycle name, so the
-    /// cycle `Long rest` ending fires `on-long-rest-end`. The two
+    /// cycle `Long rest` ending fires `on-long-rest-end`. The
     /// timer-wide events carry a fixed name.
     pub fn hook_name(&self) -> String {
         let suffix = match self {
             Self::Started => return String::from("on-timer-start"),
             Self::Stopped => return String::from("on-timer-stop"),
+            Self::GoalReached => return String::from("on-goal-reached"),
+            Self::LoopBegan(_) => return String::from("on-loop-begin"),
+            Self::LoopEnded(_) => return String::from("on-loop-end"),
+            Self::Completed => return String::from("on-timer-complete"),
             Self::Began(_) => "begin",
             Self::Running(_) => "running",
             Self::Set(_) => "set",
+            Self::Extended(_) => "extend",
             Self::Paused(_) => "pause",
             Self::Resumed(_) => "resume",
             Self::Ended(_) => "end",
+            Self::Awaiting(_) => "await",
+            Self::Overtime(_) => "overtime",
+            Self::Labeled(_) => "label",
+            Self::Interrupted(_) => "interrupt",
         };
 
         let name = match self.cycle() {
@@ -67,19 +79,42 @@ pub enum TimerHook {
 }
 
 impl TimerHook {
-    /// Runs the reaction and waits for it to complete, logging whatever
-    /// goes wrong.
+    /// Runs the reaction to `event` and waits for it to complete,
+    /// logging whatever goes wrong.
+    ///
+    /// A command learns what the timer runs for from its environment:
+    /// `COMODORO_TASK` holds the task of the cycle the event concerns,
+    /// and `COMODORO_TAGS` its tags joined with commas, both absent when
+    /// the timer carries none. A loop event sets `COMODORO_LOOP` to the
+    /// zero-based index of its loop.
     ///
     /// A hook reacts to the timer, it is never a step of it, so it
     /// reports nothing back: a command that cannot be run, one that
     /// exits non-zero, and a notification that reaches no daemon are
     /// all logged and left there. Returning no error is what makes it
     /// impossible for a hook to stop the timer.
-    pub fn execute(&mut self) {
+    pub fn execute(&mut self, event: &TimerEvent) {
         match self {
             Self::Command(cmd) => {
                 debug!("begin hook command execution");
 
+                let cycle = event.cycle();
+
+                match cycle.and_then(|cycle| cycle.task.as_ref()) {
+                    Some(task) => cmd.env("COMODORO_TASK", task),
+                    None => cmd.env_remove("COMODORO_TASK"),
+                };
+
+                match cycle.filter(|cycle| !cycle.tags.is_empty()) {
+                    Some(cycle) => cmd.env("COMODORO_TAGS", cycle.tags.join(",")),
+                    None => cmd.env_remove("COMODORO_TAGS"),
+                };
+
+                match event.loop_index() {
+                    Some(index) => cmd.env("COMODORO_LOOP", index.to_string()),
+                    None => cmd.env_remove("COMODORO_LOOP"),
+                };
+
                 match cmd.status() {
                     Ok(status) if status.success() => {}
                     Ok(status) => warn!("hook command exited with {status}"),
@@ -7,21 +7,35 @@
 //! payload. [`JsonSchemaCommand`] writes one `<key>.json` file per
 //! entry.
 //!
-//! The commands driving the timer, `start`, `pause`, `resume`, `stop`
-//! and `set`, report a confirmation rather than data, so they carry no
-//! schema: what they did travels as timer events on the wire, described
-//! in cairn/spec/protocol.md.
+//! The commands driving the timer, `start`, `pause`, `resume`, `stop`,
+//! `set`, `extend`, `skip`, `goto`, `label` and `interrupt`, report a
+//! confirmation rather than data, so they carry no schema: what they
+//! did travels as timer events on the wire, described in
+//! cairn/spec/protocol.md.
+//!
+//! That wire gets an entry of its own, `comodoro-openrpc`, keyed after
+//! no command: the OpenRPC document a server returns for
+//! `rpc.discover`, which describes every method and notification for
+//! clients written in other languages.
 //!
 //! [`JsonSchemaCommand`]: pimalaya_cli::clap::commands::JsonSchemaCommand
 
-use alloc::string::{String, ToString};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use std::collections::BTreeMap;
 
 use schemars::schema_for;
 use serde_json::Value;
 
-use crate::{cli::configure::GeneratedConfig, timer::Timer};
+use crate::{
+    cli::{configure::GeneratedConfig, history::report::Report},
+    protocol::openrpc,
+    server::history::TimerRecord,
+    timer::Timer,
+};
 
 /// Builds the command-to-schema map consumed by `json-schema <DIR>`.
 ///
@@ -45,6 +59,11 @@ pub fn generate() -> BTreeMap<String, Value> {
     insert!("comodoro-get", Timer);
     insert!("comodoro-watch", Timer);
     insert!("comodoro-configure", GeneratedConfig);
+    insert!("comodoro-report", Report);
+    // `export` renders the records in the format asked for, and prints
+    // them as the history holds them under `--json`.
+    insert!("comodoro-export", Vec<TimerRecord>);
+    schemas.insert("comodoro-openrpc".to_string(), openrpc::document());
 
     schemas
 }
@@ -31,9 +31,21 @@ impl TimerServerStartCommand {
     /// the timer emits, until the server is killed.
     pub fn execute(self, account: &mut Account) -> Result<()> {
         let addresses = account.addresses(&self.transports);
+
+        // NOTE: a hook bound to a periodic event needs the tick every
+        // second the server otherwise only keeps for its subscribers.
+        let ticks = account
+            .hooks
+            .keys()
+            .any(|name| name.ends_with("-running") || name.ends_with("-overtime"));
+
         let events = TimerServer {
-            schedule: account.schedule.clone(),
-            addresses: addresses.clone(),
+            ticks,
+            jump: account.clock_jump,
+            store: account.state.clone(),
+            history: account.history.clone(),
+            goal: account.goal.clone(),
+            ..TimerServer::new(account.schedule.clone(), addresses.clone())
         }
         .serve()?;
 
@@ -52,7 +64,7 @@ impl TimerServerStartCommand {
 
             info!("run hook {name}");
 
-            hook.execute();
+            hook.execute(&event);
         }
 
         Ok(())
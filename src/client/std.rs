@@ -14,9 +14,14 @@ use anyhow::{Context, Result, bail};
 use log::{debug, trace};
 
 use crate::{
-    jsonrpc20::{Jsonrpc20Id, Jsonrpc20Outcome, Jsonrpc20Request, Jsonrpc20Response},
-    protocol::{TimerRequest, TimerResponse},
-    timer::{Timer, TimerEvent},
+    jsonrpc20::{
+        Jsonrpc20Error, Jsonrpc20Id, Jsonrpc20Outcome, Jsonrpc20Request, Jsonrpc20Response,
+    },
+    protocol::{
+        TimerCompatibility, TimerLabelParams, TimerProtocolVersion, TimerRequest, TimerResponse,
+        TimerServerInfo, TimerSubscribeParams,
+    },
+    timer::{Timer, TimerCycleRef, TimerEvent, TimerInterruption},
     transport::{TimerAddress, TimerStream},
 };
 
@@ -28,11 +33,23 @@ use crate::{
 /// so any notification met while waiting for a response is buffered
 /// rather than dropped. That is what lets a subscribed connection keep
 /// issuing requests without losing a single event.
+///
+/// A request the server refuses fails with the
+/// [`crate::jsonrpc20::Jsonrpc20Error`] it answered, such as pausing a
+/// timer already paused, and the error reads back as a
+/// [`crate::timer::TimerError`] through its code.
+///
+/// A client connected through [`Self::connect_checked`] knows which
+/// protocol version the server speaks and which methods it answers,
+/// so a caller can ask [`Self::supports`] before relying on a method
+/// added since.
 pub struct TimerClient {
     reader: BufReader<TimerStream>,
     writer: TimerStream,
     pending: VecDeque<TimerEvent>,
     id: i64,
+    server: Option<TimerServerInfo>,
+    compatibility: Option<TimerCompatibility>,
 }
 
 impl TimerClient {
@@ -46,20 +63,115 @@ impl TimerClient {
             writer: stream,
             pending: VecDeque::new(),
             id: 0,
+            server: None,
+            compatibility: None,
         })
     }
 
+    /// Connects to the server listening at the given address, then
+    /// checks it speaks a protocol version this client gets along with.
+    ///
+    /// Fails when the server speaks another major version. The outcome
+    /// of the check is kept, see [`Self::compatibility`].
+    pub fn connect_checked(address: &TimerAddress) -> Result<Self> {
+        let mut client = Self::connect(address)?;
+
+        if client.handshake()? == TimerCompatibility::Incompatible {
+            let server = client.server.as_ref().map(|info| info.protocol);
+            let server = server.unwrap_or(TimerProtocolVersion::LEGACY);
+            let client = TimerProtocolVersion::CURRENT;
+            bail!("Timer server speaks protocol {server}, incompatible with {client}");
+        }
+
+        Ok(client)
+    }
+
+    /// Asks the server what it is, and keeps the answer.
+    ///
+    /// A server predating [`TimerRequest::Info`] answers it with an
+    /// unknown method error, and is taken for one speaking
+    /// [`TimerProtocolVersion::LEGACY`].
+    pub fn handshake(&mut self) -> Result<TimerCompatibility> {
+        let compatibility = match self.info() {
+            Ok(info) => {
+                let compatibility = TimerProtocolVersion::CURRENT.compatibility(&info.protocol);
+                self.server = Some(info);
+                compatibility
+            }
+            Err(err) if is_unknown_method(&err) => {
+                debug!("timer server predates {}", TimerRequest::INFO);
+                self.server = None;
+                TimerProtocolVersion::CURRENT.compatibility(&TimerProtocolVersion::LEGACY)
+            }
+            Err(err) => return Err(err),
+        };
+
+        self.compatibility = Some(compatibility);
+        Ok(compatibility)
+    }
+
+    /// Returns what the server says about itself.
+    pub fn info(&mut self) -> Result<TimerServerInfo> {
+        match self.send(TimerRequest::Info)? {
+            TimerResponse::Info(info) => Ok(info),
+            other => bail!("Invalid response {other:?}, expected server info"),
+        }
+    }
+
+    /// Returns what the server said about itself during the last
+    /// [`Self::handshake`], if it answered.
+    pub fn server_info(&self) -> Option<&TimerServerInfo> {
+        self.server.as_ref()
+    }
+
+    /// Returns the outcome of the last [`Self::handshake`], or `None`
+    /// without one.
+    pub fn compatibility(&self) -> Option<TimerCompatibility> {
+        self.compatibility
+    }
+
+    /// Whether the server answers `method`, as far as the last
+    /// [`Self::handshake`] tells.
+    ///
+    /// Without a handshake, every method is assumed supported.
+    pub fn supports(&self, method: &str) -> bool {
+        match (&self.server, self.compatibility) {
+            (Some(info), _) => info.supports(method),
+            (None, Some(_)) => TimerRequest::LEGACY_METHODS.contains(&method),
+            (None, None) => true,
+        }
+    }
+
     /// Returns the timer without touching it.
     pub fn get(&mut self) -> Result<Timer> {
         match self.send(TimerRequest::Get)? {
-            TimerResponse::Timer(timer) => Ok(timer),
+            TimerResponse::Timer(timer) => Ok(*timer),
             other => bail!("Invalid response {other:?}, expected a timer"),
         }
     }
 
     /// Starts the timer from its first cycle.
     pub fn start(&mut self) -> Result<Vec<TimerEvent>> {
-        self.events(TimerRequest::Start)
+        self.start_labeled(TimerLabelParams::default())
+    }
+
+    /// Starts the timer from its first cycle, running for the task
+    /// `label` names.
+    ///
+    /// A timer already running keeps its task, as it keeps its cycle.
+    pub fn start_labeled(&mut self, label: TimerLabelParams) -> Result<Vec<TimerEvent>> {
+        self.events(TimerRequest::Start { label })
+    }
+
+    /// Labels the timer with the task it runs for, replacing the task
+    /// and tags it carried.
+    pub fn label(&mut self, label: TimerLabelParams) -> Result<Vec<TimerEvent>> {
+        self.events(TimerRequest::Label { label })
+    }
+
+    /// Records an interruption of the current cycle.
+    pub fn interrupt(&mut self, interruption: TimerInterruption) -> Result<Vec<TimerEvent>> {
+        self.events(TimerRequest::Interrupt { interruption })
     }
 
     /// Pauses the timer, keeping the elapsed time.
@@ -85,11 +197,43 @@ impl TimerClient {
         self.events(TimerRequest::Set { duration })
     }
 
+    /// Ends the current cycle and begins the next one.
+    pub fn skip(&mut self) -> Result<Vec<TimerEvent>> {
+        self.events(TimerRequest::Skip)
+    }
+
+    /// Extends the current cycle by `delta` seconds, or shortens it when
+    /// negative.
+    ///
+    /// The returned event carries the new remaining duration.
+    pub fn extend(&mut self, delta: isize) -> Result<Vec<TimerEvent>> {
+        self.events(TimerRequest::Extend { delta })
+    }
+
+    /// Moves the timer to the start of the given cycle, in the given
+    /// zero-based loop or in the current one.
+    ///
+    /// Starts a stopped timer there. The server refuses a cycle or a
+    /// loop its schedule does not hold.
+    pub fn goto(
+        &mut self,
+        cycle: TimerCycleRef,
+        loop_index: Option<usize>,
+    ) -> Result<Vec<TimerEvent>> {
+        self.events(TimerRequest::Goto { cycle, loop_index })
+    }
+
     /// Subscribes this connection to the timer notifications.
     ///
     /// Call [`Self::next_event`] afterwards to consume them.
     pub fn subscribe(&mut self) -> Result<()> {
-        match self.send(TimerRequest::Subscribe)? {
+        self.subscribe_filtered(TimerSubscribeParams::default())
+    }
+
+    /// Subscribes this connection to the timer notifications `filter`
+    /// lets through, replacing the filter of an earlier subscription.
+    pub fn subscribe_filtered(&mut self, filter: TimerSubscribeParams) -> Result<()> {
+        match self.send(TimerRequest::Subscribe { filter })? {
             TimerResponse::Subscription(true) => Ok(()),
             other => bail!("Invalid response {other:?}, expected a subscription"),
         }
@@ -103,6 +247,17 @@ impl TimerClient {
         }
     }
 
+    /// Returns the OpenRPC document the server describes itself with.
+    ///
+    /// Only with the `schemars` feature, as the request it sends.
+    #[cfg(feature = "schemars")]
+    pub fn discover(&mut self) -> Result<serde_json::Value> {
+        match self.send(TimerRequest::Discover)? {
+            TimerResponse::Document(document) => Ok(document),
+            other => bail!("Invalid response {other:?}, expected a document"),
+        }
+    }
+
     /// Blocks until the next event, or returns `None` at end of stream.
     ///
     /// Requires a prior [`Self::subscribe`], since a server pushes
@@ -160,7 +315,7 @@ impl TimerClient {
             }
 
             return match response.outcome {
-                Jsonrpc20Outcome::Error(err) => bail!("{err}"),
+                Jsonrpc20Outcome::Error(err) => Err(err.into()),
                 Jsonrpc20Outcome::Result(value) => TimerResponse::from_value(&request, value)
                     .context("Deserialize timer response error"),
             };
@@ -199,3 +354,9 @@ impl TimerClient {
         Ok(Some(line))
     }
 }
+
+/// Whether `err` is the server answering it does not know the method.
+fn is_unknown_method(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<Jsonrpc20Error>()
+        .is_some_and(|err| err.code == Jsonrpc20Error::METHOD_NOT_FOUND)
+}
@@ -263,6 +263,8 @@ impl fmt::Display for Jsonrpc20Error {
     }
 }
 
+impl core::error::Error for Jsonrpc20Error {}
+
 /// One request or a batch of them, as a peer may send either.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
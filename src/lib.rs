@@ -21,8 +21,12 @@
 //!
 //! The blocking layer arrives with the `client` and `server` features.
 //! [`transport`] resolves, opens and accepts connections,
-//! [`client::std`] drives a server over one of them, and [`server::std`]
-//! owns the timer, answers requests and pushes notifications.
+//! [`client::std`] drives a server over one of them, [`server::std`]
+//! owns the timer, answers requests and pushes notifications,
+//! [`server::clock`] is the clock it runs on, real or virtual,
+//! [`server::store`] the file it keeps the timer in across restarts,
+//! [`server::history`] the record of the cycles it ran, and
+//! [`server::goal`] the daily goal it counts them towards.
 //!
 //! Both sit in a module named after the runtime they are written
 //! against, so an asynchronous port lands beside them as `client::tokio`
@@ -54,24 +58,47 @@
 //! | Method | Parameters | Result |
 //! |---|---|---|
 //! | `timer.get` | none | the timer |
-//! | `timer.start` | none | the events it emitted |
+//! | `timer.start` | optional `task` and `tags` | the events it emitted |
 //! | `timer.pause` | none | the events it emitted |
 //! | `timer.resume` | none | the events it emitted |
 //! | `timer.stop` | none | the events it emitted |
 //! | `timer.set` | `duration` in seconds | the events it emitted |
-//! | `timer.subscribe` | none | whether the connection is subscribed |
+//! | `timer.extend` | `delta` in seconds, negative to shorten | the events it emitted |
+//! | `timer.skip` | none | the events it emitted |
+//! | `timer.goto` | `cycle` by index or name, optional `loop` | the events it emitted |
+//! | `timer.label` | optional `task` and `tags` | the events it emitted |
+//! | `timer.interrupt` | `kind`, optional `note` | the events it emitted |
+//! | `timer.subscribe` | optional `include` and `exclude` filters, optional `ticks` | whether the connection is subscribed |
 //! | `timer.unsubscribe` | none | whether the connection is subscribed |
+//! | `server.info` | none | the version, protocol, methods and features of the server |
+//! | `rpc.discover` | none | the OpenRPC document of this surface |
 //!
 //! Notifications flow the other way, are named after the past tense of
 //! what just happened, and reach only the connections that asked for
 //! them with `timer.subscribe`. A connection receives `timer.started`,
-//! `timer.began`, `timer.running`, `timer.durationSet`, `timer.paused`,
-//! `timer.resumed`, `timer.ended` and `timer.stopped` as the timer
-//! changes, each carrying the cycle it concerns except the two
-//! timer-wide ones. Naming the two directions differently is what keeps
-//! a request method and a notification method from ever colliding.
-//!
-//! Failures come back as the standard codes, and only those.
+//! `timer.began`, `timer.running`, `timer.durationSet`,
+//! `timer.extended`, `timer.paused`, `timer.resumed`, `timer.ended`,
+//! `timer.awaiting`, `timer.overtime`, `timer.labeled`,
+//! `timer.interrupted`, `timer.goalReached`, `timer.loopBegan`,
+//! `timer.loopEnded`, `timer.completed` and `timer.stopped` as the
+//! timer changes, each carrying the cycle or the loop it concerns
+//! except the timer-wide ones.
+//! Naming the two directions differently is what keeps a request method
+//! and a notification method from ever colliding.
+//!
+//! [`protocol::openrpc`] builds the OpenRPC document `rpc.discover`
+//! returns from the types above, so a client in another language can be
+//! generated from a running server. It needs the `schemars` feature: a
+//! server built without it answers `rpc.discover` with -32601.
+//!
+//! The protocol is versioned apart from the crate, as `major.minor`,
+//! and `server.info` says which version a server speaks.
+//! [`client::std::TimerClient::connect_checked`] asks it on connect and
+//! refuses a server of another major version.
+//!
+//! Failures come back as the standard codes, or as one of the codes
+//! Comodoro defines for a request the timer refuses in its current
+//! state, carrying the refusal and that state as `data`.
 //!
 //! | Code | Meaning |
 //! |---|---|
@@ -80,7 +107,11 @@
 //! | -32601 | the method is unknown |
 //! | -32602 | the parameters are missing or wrong |
 //! | -32603 | the server failed internally |
-//! | -32000 to -32099 | reserved for Comodoro, unused today |
+//! | -32001 | the timer is stopped |
+//! | -32002 | the timer is not running |
+//! | -32003 | the timer is already running |
+//! | -32004 | the current cycle counts up |
+//! | -32005 | the cycles add up to no time |
 //!
 //! ## Where to look next
 //!
@@ -12,23 +12,43 @@
 //! happened (`timer.started`), so the two directions never collide.
 //!
 //! A notification identifies its event by its method name, and carries
-//! only the cycle the event concerns, since repeating the kind in the
-//! parameters would be redundant. A result cannot do that, having no
-//! method name of its own, so the events inside a result carry their
-//! own `event` discriminator.
+//! only the cycle or the loop the event concerns, since repeating the
+//! kind in the parameters would be redundant. A result cannot do that,
+//! having no method name of its own, so the events inside a result
+//! carry their own `event` discriminator.
+//!
+//! With the `schemars` feature, [`openrpc`] describes this whole
+//! surface as the OpenRPC document `rpc.discover` returns.
 //!
 //! Codes -32000 to -32099 are reserved by the specification for
-//! server-defined errors. Comodoro defines none today: every failure
-//! it can report is one of the standard codes.
+//! server-defined errors. Comodoro uses -32001 to -32005 for the
+//! requests the timer refuses in its current state, one code per
+//! [`TimerError`], and carries the refusal and the state in `data` as
+//! `{"error": "notRunning", "state": "Paused"}`, so a client can react
+//! without parsing the message. A request outside the schedule or out
+//! of range is wrong whatever the state, and is answered -32602.
+
+#[cfg(feature = "schemars")]
+pub mod openrpc;
 
-use alloc::vec::Vec;
+use core::fmt;
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 use crate::{
     jsonrpc20::{Jsonrpc20Error, Jsonrpc20Id, Jsonrpc20Request},
-    timer::{Timer, TimerCycle, TimerEvent},
+    timer::{
+        Timer, TimerCycleRef, TimerCycleRun, TimerError, TimerEvent, TimerInterruption,
+        TimerPrecision, TimerState,
+    },
 };
 
 /// A method call a client sends to a timer server.
@@ -37,7 +57,13 @@ pub enum TimerRequest {
     /// Returns the timer without touching it.
     Get,
     /// Starts the timer from its first cycle.
-    Start,
+    ///
+    /// A task given labels the run it begins, as [`Self::Label`] would.
+    /// Refused on a timer already running, task included.
+    Start {
+        /// The task the timer runs for, and its tags.
+        label: TimerLabelParams,
+    },
     /// Pauses the timer, keeping the elapsed time.
     Pause,
     /// Resumes a paused timer.
@@ -46,17 +72,80 @@ pub enum TimerRequest {
     Stop,
     /// Overrides the remaining duration of the current cycle.
     ///
-    /// Clamped to the configured length of that cycle, and ignored
-    /// entirely on a stopped timer. The resulting event carries the
+    /// Clamped to the configured length of that cycle, and refused on
+    /// a timer running no cycle. The resulting event carries the
     /// effective duration.
     Set {
         /// The new remaining duration, in seconds.
         duration: usize,
     },
+    /// Ends the current cycle and begins the next one.
+    ///
+    /// Emits what a tick crossing the boundary would, so the hooks fire
+    /// as for a natural ending. Refused on a stopped timer.
+    Skip,
+    /// Extends the current cycle, or shortens it when negative.
+    ///
+    /// Unlike [`Self::Set`], not bound by the configured length of the
+    /// cycle: the schedule after it shifts instead. Refused on a timer
+    /// running no cycle. The resulting event carries the new remaining
+    /// duration.
+    Extend {
+        /// The seconds to add, or to remove when negative.
+        delta: isize,
+    },
+    /// Moves the timer to the start of the given cycle.
+    ///
+    /// Starts a stopped timer there, and leaves a paused one paused.
+    /// A cycle the schedule does not hold is refused as invalid
+    /// parameters.
+    Goto {
+        /// The cycle to move to, by index or by name.
+        cycle: TimerCycleRef,
+        /// The zero-based loop to move to, the current one when absent.
+        loop_index: Option<usize>,
+    },
+    /// Labels the timer with the task it runs for, replacing the task
+    /// and tags it carried.
+    ///
+    /// Every cycle carries them from then on, until the timer stops. A
+    /// stopped timer keeps them for the next run.
+    Label {
+        /// The task the timer runs for, and its tags.
+        label: TimerLabelParams,
+    },
+    /// Records an interruption of the current cycle.
+    ///
+    /// Refused on a stopped or waiting timer, which runs no cycle.
+    Interrupt {
+        /// Where the interruption came from, and what it was about.
+        interruption: TimerInterruption,
+    },
     /// Subscribes this connection to the timer notifications.
-    Subscribe,
+    ///
+    /// Subscribing again replaces the filter of the earlier
+    /// subscription.
+    Subscribe {
+        /// The notifications the connection receives, every one when
+        /// empty.
+        filter: TimerSubscribeParams,
+    },
     /// Stops sending timer notifications on this connection.
     Unsubscribe,
+    /// Returns what the server is: its version, the protocol version it
+    /// speaks, the methods it answers and the features it runs with.
+    ///
+    /// Named after the server rather than the timer, since it describes
+    /// the former.
+    Info,
+    /// Returns the OpenRPC document describing this very surface.
+    ///
+    /// Named `rpc.discover` rather than after the timer, as the OpenRPC
+    /// specification names its service discovery method. Only with the
+    /// `schemars` feature, which the document is built from: a server
+    /// without it answers the method as unknown.
+    #[cfg(feature = "schemars")]
+    Discover,
 }
 
 impl TimerRequest {
@@ -72,22 +161,79 @@ impl TimerRequest {
     pub const STOP: &'static str = "timer.stop";
     /// Method name of [`Self::Set`].
     pub const SET: &'static str = "timer.set";
+    /// Method name of [`Self::Skip`].
+    pub const SKIP: &'static str = "timer.skip";
+    /// Method name of [`Self::Extend`].
+    pub const EXTEND: &'static str = "timer.extend";
+    /// Method name of [`Self::Goto`].
+    pub const GOTO: &'static str = "timer.goto";
+    /// Method name of [`Self::Label`].
+    pub const LABEL: &'static str = "timer.label";
+    /// Method name of [`Self::Interrupt`].
+    pub const INTERRUPT: &'static str = "timer.interrupt";
     /// Method name of [`Self::Subscribe`].
     pub const SUBSCRIBE: &'static str = "timer.subscribe";
     /// Method name of [`Self::Unsubscribe`].
     pub const UNSUBSCRIBE: &'static str = "timer.unsubscribe";
+    /// Method name of [`Self::Info`].
+    pub const INFO: &'static str = "server.info";
+    /// Method name of [`Self::Discover`].
+    #[cfg(feature = "schemars")]
+    pub const DISCOVER: &'static str = "rpc.discover";
+
+    /// Every method name a server answers, in the order the
+    /// specification lists them.
+    pub const METHODS: &'static [&'static str] = &[
+        Self::GET,
+        Self::START,
+        Self::PAUSE,
+        Self::RESUME,
+        Self::STOP,
+        Self::SET,
+        Self::EXTEND,
+        Self::SKIP,
+        Self::GOTO,
+        Self::LABEL,
+        Self::INTERRUPT,
+        Self::SUBSCRIBE,
+        Self::UNSUBSCRIBE,
+        Self::INFO,
+        #[cfg(feature = "schemars")]
+        Self::DISCOVER,
+    ];
+
+    /// Every method name a server predating [`Self::Info`] answers,
+    /// which is protocol 1.0.
+    pub const LEGACY_METHODS: &'static [&'static str] = &[
+        Self::GET,
+        Self::START,
+        Self::PAUSE,
+        Self::RESUME,
+        Self::STOP,
+        Self::SET,
+        Self::SUBSCRIBE,
+        Self::UNSUBSCRIBE,
+    ];
 
     /// The method name carrying this request on the wire.
     pub fn method(&self) -> &'static str {
         match self {
             Self::Get => Self::GET,
-            Self::Start => Self::START,
+            Self::Start { .. } => Self::START,
             Self::Pause => Self::PAUSE,
             Self::Resume => Self::RESUME,
             Self::Stop => Self::STOP,
             Self::Set { .. } => Self::SET,
-            Self::Subscribe => Self::SUBSCRIBE,
+            Self::Skip => Self::SKIP,
+            Self::Extend { .. } => Self::EXTEND,
+            Self::Goto { .. } => Self::GOTO,
+            Self::Label { .. } => Self::LABEL,
+            Self::Interrupt { .. } => Self::INTERRUPT,
+            Self::Subscribe { .. } => Self::SUBSCRIBE,
             Self::Unsubscribe => Self::UNSUBSCRIBE,
+            Self::Info => Self::INFO,
+            #[cfg(feature = "schemars")]
+            Self::Discover => Self::DISCOVER,
         }
     }
 
@@ -95,6 +241,18 @@ impl TimerRequest {
     pub fn params(&self) -> Option<Value> {
         match self {
             Self::Set { duration } => Some(json!({ "duration": duration })),
+            Self::Extend { delta } => Some(json!({ "delta": delta })),
+            Self::Goto { cycle, loop_index } => Some(json!(TimerGotoParams {
+                cycle: cycle.clone(),
+                loop_index: *loop_index,
+            })),
+            // NOTE: a start labeling nothing carries no parameters, so
+            // it reads the same as before starts could label.
+            Self::Start { label } if label.is_empty() => None,
+            Self::Start { label } | Self::Label { label } => Some(json!(label)),
+            Self::Subscribe { filter } if filter.is_empty() => None,
+            Self::Subscribe { filter } => Some(json!(filter)),
+            Self::Interrupt { interruption } => Some(json!(interruption)),
             _ => None,
         }
     }
@@ -111,12 +269,23 @@ impl TimerRequest {
     pub fn from_jsonrpc(request: &Jsonrpc20Request) -> Result<Self, Jsonrpc20Error> {
         match request.method.as_str() {
             Self::GET => Ok(Self::Get),
-            Self::START => Ok(Self::Start),
+            Self::START => Ok(Self::Start {
+                label: label_params(request)?,
+            }),
+            Self::LABEL => Ok(Self::Label {
+                label: label_params(request)?,
+            }),
             Self::PAUSE => Ok(Self::Pause),
             Self::RESUME => Ok(Self::Resume),
             Self::STOP => Ok(Self::Stop),
-            Self::SUBSCRIBE => Ok(Self::Subscribe),
+            Self::SKIP => Ok(Self::Skip),
+            Self::SUBSCRIBE => Ok(Self::Subscribe {
+                filter: subscribe_params(request)?,
+            }),
             Self::UNSUBSCRIBE => Ok(Self::Unsubscribe),
+            Self::INFO => Ok(Self::Info),
+            #[cfg(feature = "schemars")]
+            Self::DISCOVER => Ok(Self::Discover),
             Self::SET => {
                 let params = request.params.clone().unwrap_or(Value::Null);
                 let params: TimerSetParams = serde_json::from_value(params).map_err(|err| {
@@ -126,12 +295,89 @@ impl TimerRequest {
                     duration: params.duration,
                 })
             }
+            Self::EXTEND => {
+                let params = request.params.clone().unwrap_or(Value::Null);
+                let params: TimerExtendParams = serde_json::from_value(params).map_err(|err| {
+                    Jsonrpc20Error::invalid_params(err).with_data(json!(["delta"]))
+                })?;
+                Ok(Self::Extend {
+                    delta: params.delta,
+                })
+            }
+            Self::GOTO => {
+                let params = request.params.clone().unwrap_or(Value::Null);
+                let params: TimerGotoParams = serde_json::from_value(params).map_err(|err| {
+                    Jsonrpc20Error::invalid_params(err).with_data(json!(["cycle"]))
+                })?;
+                Ok(Self::Goto {
+                    cycle: params.cycle,
+                    loop_index: params.loop_index,
+                })
+            }
+            Self::INTERRUPT => {
+                let params = request.params.clone().unwrap_or(Value::Null);
+                let interruption = serde_json::from_value(params).map_err(|err| {
+                    Jsonrpc20Error::invalid_params(err).with_data(json!(["kind", "note"]))
+                })?;
+                Ok(Self::Interrupt { interruption })
+            }
             method => Err(Jsonrpc20Error::method_not_found(method)),
         }
     }
 }
 
+/// Reads the parameters of a request labeling the timer, none reading
+/// as a label without task nor tags.
+fn label_params(request: &Jsonrpc20Request) -> Result<TimerLabelParams, Jsonrpc20Error> {
+    let Some(params) = request.params.clone() else {
+        return Ok(TimerLabelParams::default());
+    };
+
+    serde_json::from_value(params)
+        .map_err(|err| Jsonrpc20Error::invalid_params(err).with_data(json!(["task", "tags"])))
+}
+
+/// Reads the parameters of a subscription, none reading as a
+/// subscription to every notification.
+///
+/// Refuses an event kind no notification goes by, which would filter
+/// nothing and most likely is a typo.
+fn subscribe_params(request: &Jsonrpc20Request) -> Result<TimerSubscribeParams, Jsonrpc20Error> {
+    let Some(params) = request.params.clone() else {
+        return Ok(TimerSubscribeParams::default());
+    };
+
+    let params: TimerSubscribeParams = serde_json::from_value(params).map_err(|err| {
+        Jsonrpc20Error::invalid_params(err).with_data(json!(["include", "exclude"]))
+    })?;
+
+    let kinds = params.include.events.iter().chain(&params.exclude.events);
+
+    for kind in kinds {
+        if !TimerEvent::METHODS
+            .iter()
+            .any(|method| kind_of(method) == kind)
+        {
+            let err = format!("Unknown event kind `{kind}`");
+            return Err(Jsonrpc20Error::invalid_params(err).with_data(json!(["events"])));
+        }
+    }
+
+    if let Some(TimerTicks::Every(0)) = params.ticks {
+        let err = "Ticks cannot come every 0 seconds";
+        return Err(Jsonrpc20Error::invalid_params(err).with_data(json!(["ticks"])));
+    }
+
+    Ok(params)
+}
+
+/// The kind a notification method goes by in a subscription filter.
+fn kind_of(method: &str) -> &str {
+    method.strip_prefix("timer.").unwrap_or(method)
+}
+
 /// Parameters of the [`TimerRequest::Set`] method.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TimerSetParams {
@@ -139,15 +385,162 @@ pub struct TimerSetParams {
     pub duration: usize,
 }
 
+/// Parameters of the [`TimerRequest::Extend`] method.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimerExtendParams {
+    /// The seconds to add to the current cycle, or to remove from it
+    /// when negative.
+    pub delta: isize,
+}
+
+/// Parameters of the [`TimerRequest::Goto`] method.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimerGotoParams {
+    /// The cycle to move to, a zero-based index or a name.
+    pub cycle: TimerCycleRef,
+    /// The zero-based loop to move to, the current one when absent.
+    #[serde(rename = "loop", default, skip_serializing_if = "Option::is_none")]
+    pub loop_index: Option<usize>,
+}
+
+/// Parameters of the [`TimerRequest::Start`] and [`TimerRequest::Label`]
+/// methods.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimerLabelParams {
+    /// The free-text task the timer runs for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// The tags of the task.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl TimerLabelParams {
+    /// Whether the label holds neither task nor tags.
+    pub fn is_empty(&self) -> bool {
+        self.task.is_none() && self.tags.is_empty()
+    }
+}
+
+/// Parameters of the [`TimerRequest::Subscribe`] method, narrowing the
+/// notifications the connection receives.
+///
+/// A notification is received when `include` lets it through and
+/// `exclude` does not name it. The cycle lists only apply to the
+/// notifications concerning a cycle, so `{"include": {"cycles":
+/// ["Work"]}}` still receives `timer.started` and `timer.stopped`.
+///
+/// Of the ticks it lets through, `ticks` thins out the ones a
+/// subscriber would receive every second.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimerSubscribeParams {
+    /// The notifications to receive, every one when empty.
+    #[serde(default, skip_serializing_if = "TimerEventFilter::is_empty")]
+    pub include: TimerEventFilter,
+    /// The notifications not to receive, even when included.
+    #[serde(default, skip_serializing_if = "TimerEventFilter::is_empty")]
+    pub exclude: TimerEventFilter,
+    /// How often to receive `timer.running` and `timer.overtime`,
+    /// every second when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticks: Option<TimerTicks>,
+}
+
+impl TimerSubscribeParams {
+    /// Whether the subscription filters nothing out.
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && self.ticks.is_none()
+    }
+
+    /// Whether the connection receives the notification of `event`.
+    pub fn matches(&self, event: &TimerEvent) -> bool {
+        let kind = event.kind();
+        let cycle = event.cycle().map(|cycle| cycle.name.as_str());
+
+        let events = |list: &[String]| list.iter().any(|event| event == kind);
+        let cycles = |list: &[String]| cycle.is_some_and(|name| list.iter().any(|c| c == name));
+
+        let included = (self.include.events.is_empty() || events(&self.include.events))
+            && (self.include.cycles.is_empty() || cycle.is_none() || cycles(&self.include.cycles));
+        let excluded = events(&self.exclude.events) || cycles(&self.exclude.cycles);
+
+        included && !excluded
+    }
+}
+
+/// How often a subscriber receives the ticks, `timer.running` and
+/// `timer.overtime`, which a server computes every second.
+///
+/// Travels as `{"every": 10}` or `{"precision": "minute"}`.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum TimerTicks {
+    /// Every given number of seconds of the duration the cycle shows.
+    Every(usize),
+    /// Whenever the duration the cycle shows reads otherwise at the
+    /// given precision, as a status bar renders it.
+    Precision(TimerPrecision),
+}
+
+impl TimerTicks {
+    /// The step a tick carrying `duration` milliseconds falls in.
+    ///
+    /// A subscriber receives the first tick of every step, and none of
+    /// the ticks after it falling in the same one.
+    pub fn step(&self, duration: usize) -> usize {
+        match self {
+            Self::Every(seconds) => duration / 1000 / (*seconds).max(1),
+            Self::Precision(precision) => precision.truncate(duration),
+        }
+    }
+}
+
+/// Notifications a subscription names, by kind and by cycle.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimerEventFilter {
+    /// Event kinds, named after their notification method without the
+    /// `timer.` prefix, such as `began` or `durationSet`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    /// Names of the cycles the notifications concern.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<String>,
+}
+
+impl TimerEventFilter {
+    /// Whether the filter names neither event kind nor cycle.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.cycles.is_empty()
+    }
+}
+
 /// The result a server returns for a [`TimerRequest`].
 #[derive(Clone, Debug, PartialEq)]
 pub enum TimerResponse {
     /// The timer, answering [`TimerRequest::Get`].
-    Timer(Timer),
+    ///
+    /// Boxed, since a timer outweighs the other results by far.
+    Timer(Box<Timer>),
     /// The events the call made the timer emit, possibly none.
     Events(Vec<TimerEvent>),
     /// Whether the connection is now subscribed to notifications.
     Subscription(bool),
+    /// What the server is, answering [`TimerRequest::Info`].
+    Info(TimerServerInfo),
+    /// The OpenRPC document, answering [`TimerRequest::Discover`].
+    #[cfg(feature = "schemars")]
+    Document(Value),
 }
 
 impl TimerResponse {
@@ -157,6 +550,9 @@ impl TimerResponse {
             Self::Timer(timer) => json!(timer),
             Self::Events(events) => json!({ "events": events }),
             Self::Subscription(subscribed) => json!({ "subscribed": subscribed }),
+            Self::Info(info) => json!(info),
+            #[cfg(feature = "schemars")]
+            Self::Document(document) => document.clone(),
         }
     }
 
@@ -166,11 +562,16 @@ impl TimerResponse {
     /// results carry no discriminator of their own.
     pub fn from_value(request: &TimerRequest, value: Value) -> Result<Self, serde_json::Error> {
         match request {
-            TimerRequest::Get => serde_json::from_value(value).map(Self::Timer),
-            TimerRequest::Subscribe | TimerRequest::Unsubscribe => {
+            TimerRequest::Get => {
+                serde_json::from_value(value).map(|timer| Self::Timer(Box::new(timer)))
+            }
+            TimerRequest::Subscribe { .. } | TimerRequest::Unsubscribe => {
                 let params: TimerSubscriptionResult = serde_json::from_value(value)?;
                 Ok(Self::Subscription(params.subscribed))
             }
+            TimerRequest::Info => serde_json::from_value(value).map(Self::Info),
+            #[cfg(feature = "schemars")]
+            TimerRequest::Discover => Ok(Self::Document(value)),
             _ => {
                 let result: TimerEventsResult = serde_json::from_value(value)?;
                 Ok(Self::Events(result.events))
@@ -180,6 +581,7 @@ impl TimerResponse {
 }
 
 /// The result of every method that can make the timer emit events.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TimerEventsResult {
@@ -189,6 +591,7 @@ pub struct TimerEventsResult {
 
 /// The result of [`TimerRequest::Subscribe`] and
 /// [`TimerRequest::Unsubscribe`].
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TimerSubscriptionResult {
@@ -197,11 +600,141 @@ pub struct TimerSubscriptionResult {
 }
 
 /// Parameters of every notification concerning a cycle.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TimerCycleParams {
     /// The cycle the notification is about.
-    pub cycle: TimerCycle,
+    pub cycle: TimerCycleRun,
+}
+
+/// The result of [`TimerRequest::Info`].
+///
+/// Unlike the other results, it tolerates members it does not know, so
+/// a server of a later minor version can say more than a client reads.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TimerServerInfo {
+    /// The version of Comodoro the server was built from.
+    pub version: String,
+    /// The version of the protocol the server speaks.
+    pub protocol: TimerProtocolVersion,
+    /// Every method the server answers.
+    pub methods: Vec<String>,
+    /// What the server runs with, among [`Self::SOCKET`],
+    /// [`Self::TCP`], [`Self::PERSISTENCE`], [`Self::HISTORY`] and
+    /// [`Self::GOAL`], and whatever later versions add.
+    pub features: Vec<String>,
+}
+
+impl TimerServerInfo {
+    /// The server listens on a Unix domain socket.
+    pub const SOCKET: &'static str = "socket";
+    /// The server listens on TCP.
+    pub const TCP: &'static str = "tcp";
+    /// The server keeps the timer across restarts.
+    pub const PERSISTENCE: &'static str = "persistence";
+    /// The server records the cycles the timer ran.
+    pub const HISTORY: &'static str = "history";
+    /// The server counts the cycles towards a daily goal.
+    pub const GOAL: &'static str = "goal";
+
+    /// Whether the server answers `method`.
+    pub fn supports(&self, method: &str) -> bool {
+        self.methods.iter().any(|supported| supported == method)
+    }
+
+    /// Whether the server runs with `feature`.
+    pub fn has(&self, feature: &str) -> bool {
+        self.features.iter().any(|enabled| enabled == feature)
+    }
+}
+
+/// The version of the protocol a peer speaks, as `major.minor`.
+///
+/// A minor version adds methods, notifications or members, which a
+/// peer of an earlier one ignores or does without. A major version
+/// changes what the existing ones mean.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimerProtocolVersion {
+    /// Bumped when the existing methods change meaning.
+    pub major: u16,
+    /// Bumped when methods, notifications or members are added.
+    pub minor: u16,
+}
+
+impl TimerProtocolVersion {
+    /// The version this crate speaks.
+    pub const CURRENT: Self = Self { major: 1, minor: 1 };
+    /// The version a server predating [`TimerRequest::Info`] speaks.
+    pub const LEGACY: Self = Self { major: 1, minor: 0 };
+
+    /// How well a client speaking this version gets along with a
+    /// server speaking `server`.
+    pub fn compatibility(&self, server: &Self) -> TimerCompatibility {
+        if self.major != server.major {
+            TimerCompatibility::Incompatible
+        } else if self.minor > server.minor {
+            TimerCompatibility::Partial
+        } else {
+            TimerCompatibility::Full
+        }
+    }
+}
+
+impl fmt::Display for TimerProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl From<TimerProtocolVersion> for String {
+    fn from(version: TimerProtocolVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl TryFrom<String> for TimerProtocolVersion {
+    type Error = &'static str;
+
+    fn try_from(version: String) -> Result<Self, Self::Error> {
+        let (major, minor) = version
+            .split_once('.')
+            .ok_or("expected a version as major.minor")?;
+
+        Ok(Self {
+            major: major.parse().map_err(|_| "invalid major version")?,
+            minor: minor.parse().map_err(|_| "invalid minor version")?,
+        })
+    }
+}
+
+/// How well a client and a server speaking their protocol versions get
+/// along.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimerCompatibility {
+    /// The server speaks the version of the client, or a later minor
+    /// one: everything the client calls exists.
+    Full,
+    /// The server speaks an earlier minor version: what existed then
+    /// works, and the client checks the methods the server lists
+    /// before calling a later one.
+    Partial,
+    /// The server speaks another major version, whose methods may mean
+    /// something else.
+    Incompatible,
+}
+
+/// Parameters of every notification concerning a loop.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimerLoopParams {
+    /// The zero-based index of the loop the notification is about.
+    #[serde(rename = "loop")]
+    pub index: usize,
 }
 
 /// Protocol mapping of the events a timer emits.
@@ -217,15 +750,55 @@ impl TimerEvent {
     pub const RUNNING: &'static str = "timer.running";
     /// Notification method name of [`Self::Set`].
     pub const DURATION_SET: &'static str = "timer.durationSet";
+    /// Notification method name of [`Self::Extended`].
+    pub const EXTENDED: &'static str = "timer.extended";
     /// Notification method name of [`Self::Paused`].
     pub const PAUSED: &'static str = "timer.paused";
     /// Notification method name of [`Self::Resumed`].
     pub const RESUMED: &'static str = "timer.resumed";
     /// Notification method name of [`Self::Ended`].
     pub const ENDED: &'static str = "timer.ended";
+    /// Notification method name of [`Self::Awaiting`].
+    pub const AWAITING: &'static str = "timer.awaiting";
+    /// Notification method name of [`Self::Overtime`].
+    pub const OVERTIME: &'static str = "timer.overtime";
+    /// Notification method name of [`Self::Labeled`].
+    pub const LABELED: &'static str = "timer.labeled";
+    /// Notification method name of [`Self::Interrupted`].
+    pub const INTERRUPTED: &'static str = "timer.interrupted";
+    /// Notification method name of [`Self::GoalReached`].
+    pub const GOAL_REACHED: &'static str = "timer.goalReached";
+    /// Notification method name of [`Self::LoopBegan`].
+    pub const LOOP_BEGAN: &'static str = "timer.loopBegan";
+    /// Notification method name of [`Self::LoopEnded`].
+    pub const LOOP_ENDED: &'static str = "timer.loopEnded";
+    /// Notification method name of [`Self::Completed`].
+    pub const COMPLETED: &'static str = "timer.completed";
     /// Notification method name of [`Self::Stopped`].
     pub const STOPPED: &'static str = "timer.stopped";
 
+    /// Every notification method name a server pushes, in the order the
+    /// specification lists them.
+    pub const METHODS: &'static [&'static str] = &[
+        Self::STARTED,
+        Self::BEGAN,
+        Self::RUNNING,
+        Self::DURATION_SET,
+        Self::EXTENDED,
+        Self::PAUSED,
+        Self::RESUMED,
+        Self::ENDED,
+        Self::AWAITING,
+        Self::OVERTIME,
+        Self::LABELED,
+        Self::INTERRUPTED,
+        Self::GOAL_REACHED,
+        Self::LOOP_BEGAN,
+        Self::LOOP_ENDED,
+        Self::COMPLETED,
+        Self::STOPPED,
+    ];
+
     /// The notification method name carrying this event.
     pub fn method(&self) -> &'static str {
         match self {
@@ -233,30 +806,67 @@ impl TimerEvent {
             Self::Began(_) => Self::BEGAN,
             Self::Running(_) => Self::RUNNING,
             Self::Set(_) => Self::DURATION_SET,
+            Self::Extended(_) => Self::EXTENDED,
             Self::Paused(_) => Self::PAUSED,
             Self::Resumed(_) => Self::RESUMED,
             Self::Ended(_) => Self::ENDED,
+            Self::Awaiting(_) => Self::AWAITING,
+            Self::Overtime(_) => Self::OVERTIME,
+            Self::Labeled(_) => Self::LABELED,
+            Self::Interrupted(_) => Self::INTERRUPTED,
+            Self::GoalReached => Self::GOAL_REACHED,
+            Self::LoopBegan(_) => Self::LOOP_BEGAN,
+            Self::LoopEnded(_) => Self::LOOP_ENDED,
+            Self::Completed => Self::COMPLETED,
             Self::Stopped => Self::STOPPED,
         }
     }
 
+    /// The kind of this event in a subscription filter, which is its
+    /// notification method without the `timer.` prefix.
+    pub fn kind(&self) -> &'static str {
+        kind_of(self.method())
+    }
+
     /// The cycle this event concerns, when it concerns one.
-    pub fn cycle(&self) -> Option<&TimerCycle> {
+    pub fn cycle(&self) -> Option<&TimerCycleRun> {
         match self {
-            Self::Started | Self::Stopped => None,
+            Self::Started
+            | Self::Stopped
+            | Self::GoalReached
+            | Self::LoopBegan(_)
+            | Self::LoopEnded(_)
+            | Self::Completed => None,
             Self::Began(cycle)
             | Self::Running(cycle)
             | Self::Set(cycle)
+            | Self::Extended(cycle)
             | Self::Paused(cycle)
             | Self::Resumed(cycle)
-            | Self::Ended(cycle) => Some(cycle),
+            | Self::Ended(cycle)
+            | Self::Awaiting(cycle)
+            | Self::Overtime(cycle)
+            | Self::Labeled(cycle)
+            | Self::Interrupted(cycle) => Some(cycle),
+        }
+    }
+
+    /// The zero-based index of the loop this event concerns, when it
+    /// concerns one.
+    pub fn loop_index(&self) -> Option<usize> {
+        match self {
+            Self::LoopBegan(index) | Self::LoopEnded(index) => Some(*index),
+            _ => None,
         }
     }
 
     /// Wraps this event in the notification a server pushes.
     pub fn into_notification(self) -> Jsonrpc20Request {
         let method = self.method();
-        let params = self.cycle().map(|cycle| json!({ "cycle": cycle }));
+        let params = match self.loop_index() {
+            Some(index) => Some(json!({ "loop": index })),
+            None => self.cycle().map(|cycle| json!({ "cycle": cycle })),
+        };
         Jsonrpc20Request::notification(method, params)
     }
 
@@ -265,21 +875,93 @@ impl TimerEvent {
     /// Returns `None` when the method is not a timer notification, so a
     /// client can ignore what it does not understand rather than fail.
     pub fn from_notification(request: &Jsonrpc20Request) -> Option<Self> {
-        let cycle = || -> Option<TimerCycle> {
+        let cycle = || -> Option<TimerCycleRun> {
             let params = request.params.clone()?;
             let params: TimerCycleParams = serde_json::from_value(params).ok()?;
             Some(params.cycle)
         };
 
+        let loop_index = || -> Option<usize> {
+            let params = request.params.clone()?;
+            let params: TimerLoopParams = serde_json::from_value(params).ok()?;
+            Some(params.index)
+        };
+
         match request.method.as_str() {
             Self::STARTED => Some(Self::Started),
             Self::STOPPED => Some(Self::Stopped),
+            Self::GOAL_REACHED => Some(Self::GoalReached),
+            Self::COMPLETED => Some(Self::Completed),
+            Self::LOOP_BEGAN => loop_index().map(Self::LoopBegan),
+            Self::LOOP_ENDED => loop_index().map(Self::LoopEnded),
             Self::BEGAN => cycle().map(Self::Began),
             Self::RUNNING => cycle().map(Self::Running),
             Self::DURATION_SET => cycle().map(Self::Set),
+            Self::EXTENDED => cycle().map(Self::Extended),
             Self::PAUSED => cycle().map(Self::Paused),
             Self::RESUMED => cycle().map(Self::Resumed),
             Self::ENDED => cycle().map(Self::Ended),
+            Self::AWAITING => cycle().map(Self::Awaiting),
+            Self::OVERTIME => cycle().map(Self::Overtime),
+            Self::LABELED => cycle().map(Self::Labeled),
+            Self::INTERRUPTED => cycle().map(Self::Interrupted),
+            _ => None,
+        }
+    }
+}
+
+/// Protocol mapping of the refusals of a timer.
+///
+/// Lives here for the same reason as the mapping of [`TimerEvent`]:
+/// the codes are a wire concern.
+impl TimerError {
+    /// Error code of [`Self::Stopped`].
+    pub const STOPPED: i64 = -32001;
+    /// Error code of [`Self::NotRunning`].
+    pub const NOT_RUNNING: i64 = -32002;
+    /// Error code of [`Self::AlreadyRunning`].
+    pub const ALREADY_RUNNING: i64 = -32003;
+    /// Error code of [`Self::CountingUp`].
+    pub const COUNTING_UP: i64 = -32004;
+    /// Error code of [`Self::NoTime`].
+    pub const NO_TIME: i64 = -32005;
+
+    /// The server-defined code carrying this refusal.
+    ///
+    /// [`Self::OutOfSchedule`] and [`Self::OutOfRange`] refuse the
+    /// parameters rather than the state, so they travel as invalid
+    /// params, the way a server answers them.
+    pub fn code(&self) -> i64 {
+        match self {
+            Self::Stopped => Self::STOPPED,
+            Self::NotRunning => Self::NOT_RUNNING,
+            Self::AlreadyRunning => Self::ALREADY_RUNNING,
+            Self::CountingUp => Self::COUNTING_UP,
+            Self::NoTime => Self::NO_TIME,
+            Self::OutOfSchedule | Self::OutOfRange => Jsonrpc20Error::INVALID_PARAMS,
+        }
+    }
+
+    /// Wraps this refusal in the error a server answers, with the
+    /// `state` of the timer that refused.
+    pub fn into_jsonrpc(self, state: &TimerState) -> Jsonrpc20Error {
+        Jsonrpc20Error::new(self.code(), self).with_data(json!({
+            "error": self,
+            "state": state,
+        }))
+    }
+
+    /// Reads a refusal back from the error a server answered.
+    ///
+    /// Returns `None` when the code is not one of the above, so a
+    /// client can tell a refusal from any other failure.
+    pub fn from_jsonrpc(err: &Jsonrpc20Error) -> Option<Self> {
+        match err.code {
+            Self::STOPPED => Some(Self::Stopped),
+            Self::NOT_RUNNING => Some(Self::NotRunning),
+            Self::ALREADY_RUNNING => Some(Self::AlreadyRunning),
+            Self::COUNTING_UP => Some(Self::CountingUp),
+            Self::NO_TIME => Some(Self::NoTime),
             _ => None,
         }
     }
@@ -287,21 +969,79 @@ impl TimerEvent {
 
 #[cfg(test)]
 mod tests {
-    use alloc::{string::ToString, vec};
+    use alloc::{string::ToString, vec, vec::Vec};
 
-    use crate::protocol::*;
+    use crate::{
+        protocol::*,
+        timer::{TimerCycle, TimerInterruptionKind},
+    };
 
     #[test]
     fn request_round_trips_through_jsonrpc() {
         for request in [
             TimerRequest::Get,
-            TimerRequest::Start,
+            TimerRequest::Start {
+                label: TimerLabelParams::default(),
+            },
+            TimerRequest::Start {
+                label: TimerLabelParams {
+                    task: Some("review PR 42".to_string()),
+                    tags: vec!["review".to_string()],
+                },
+            },
+            TimerRequest::Label {
+                label: TimerLabelParams {
+                    task: None,
+                    tags: vec!["admin".to_string()],
+                },
+            },
             TimerRequest::Pause,
             TimerRequest::Resume,
             TimerRequest::Stop,
             TimerRequest::Set { duration: 60 },
-            TimerRequest::Subscribe,
+            TimerRequest::Skip,
+            TimerRequest::Extend { delta: 600 },
+            TimerRequest::Extend { delta: -60 },
+            TimerRequest::Goto {
+                cycle: TimerCycleRef::Index(2),
+                loop_index: None,
+            },
+            TimerRequest::Goto {
+                cycle: TimerCycleRef::Name("Long rest".to_string()),
+                loop_index: Some(1),
+            },
+            TimerRequest::Interrupt {
+                interruption: TimerInterruption {
+                    kind: TimerInterruptionKind::Internal,
+                    note: None,
+                },
+            },
+            TimerRequest::Interrupt {
+                interruption: TimerInterruption {
+                    kind: TimerInterruptionKind::External,
+                    note: Some("phone call".to_string()),
+                },
+            },
+            TimerRequest::Subscribe {
+                filter: TimerSubscribeParams::default(),
+            },
+            TimerRequest::Subscribe {
+                filter: TimerSubscribeParams {
+                    include: TimerEventFilter {
+                        events: vec!["began".to_string(), "ended".to_string()],
+                        cycles: Vec::new(),
+                    },
+                    exclude: TimerEventFilter {
+                        events: Vec::new(),
+                        cycles: vec!["Break".to_string()],
+                    },
+                    ticks: Some(TimerTicks::Precision(TimerPrecision::Minute)),
+                },
+            },
             TimerRequest::Unsubscribe,
+            TimerRequest::Info,
+            #[cfg(feature = "schemars")]
+            TimerRequest::Discover,
         ] {
             let envelope = request.clone().into_jsonrpc(1);
             let parsed = TimerRequest::from_jsonrpc(&envelope).unwrap();
@@ -319,6 +1059,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn goto_wire_shape_is_stable() {
+        let request = TimerRequest::Goto {
+            cycle: TimerCycleRef::Name("Long rest".to_string()),
+            loop_index: Some(1),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&request.into_jsonrpc(7)).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"timer.goto","params":{"cycle":"Long rest","loop":1},"id":7}"#
+        );
+    }
+
+    #[test]
+    fn start_labels_only_when_given_a_task() {
+        let start = TimerRequest::Start {
+            label: TimerLabelParams::default(),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&start.into_jsonrpc(7)).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"timer.start","id":7}"#
+        );
+
+        let start = TimerRequest::Start {
+            label: TimerLabelParams {
+                task: Some("review PR 42".to_string()),
+                tags: vec!["review".to_string()],
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_string(&start.into_jsonrpc(7)).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"timer.start","params":{"tags":["review"],"task":"review PR 42"},"id":7}"#
+        );
+    }
+
     #[test]
     fn unknown_method_is_method_not_found() {
         let envelope = Jsonrpc20Request::new("timer.explode", None, 1);
@@ -326,6 +1103,15 @@ mod tests {
         assert_eq!(err.code, Jsonrpc20Error::METHOD_NOT_FOUND);
     }
 
+    #[cfg(not(feature = "schemars"))]
+    #[test]
+    fn discovery_without_schemas_is_method_not_found() {
+        let envelope = Jsonrpc20Request::new("rpc.discover", None, 1);
+        let err = TimerRequest::from_jsonrpc(&envelope).unwrap_err();
+        assert_eq!(err.code, Jsonrpc20Error::METHOD_NOT_FOUND);
+        assert!(!TimerRequest::METHODS.contains(&"rpc.discover"));
+    }
+
     #[test]
     fn set_without_duration_is_invalid_params() {
         let envelope = Jsonrpc20Request::new(TimerRequest::SET, None, 1);
@@ -338,12 +1124,27 @@ mod tests {
         for event in [
             TimerEvent::Started,
             TimerEvent::Stopped,
-            TimerEvent::Began(TimerCycle::new("Work", 1500)),
-            TimerEvent::Running(TimerCycle::new("Work", 1499)),
-            TimerEvent::Set(TimerCycle::new("Work", 60)),
-            TimerEvent::Paused(TimerCycle::new("Work", 42)),
-            TimerEvent::Resumed(TimerCycle::new("Work", 42)),
-            TimerEvent::Ended(TimerCycle::new("Work", 0)),
+            TimerEvent::GoalReached,
+            TimerEvent::LoopBegan(1),
+            TimerEvent::LoopEnded(0),
+            TimerEvent::Completed,
+            TimerEvent::Began(TimerCycle::new("Work", 1500).into()),
+            TimerEvent::Running(TimerCycle::new("Work", 1499).into()),
+            TimerEvent::Set(TimerCycle::new("Work", 60).into()),
+            TimerEvent::Extended(TimerCycle::new("Work", 660).into()),
+            TimerEvent::Paused(TimerCycle::new("Work", 42).into()),
+            TimerEvent::Resumed(TimerCycle::new("Work", 42).into()),
+            TimerEvent::Ended(TimerCycle::new("Work", 0).into()),
+            TimerEvent::Awaiting(TimerCycle::new("Rest", 300).manual().into()),
+            TimerEvent::Overtime(TimerCycle::new("Rest", 180).overtime().into()),
+            TimerEvent::Labeled(TimerCycle::new("Work", 900).into()),
+            TimerEvent::Interrupted(TimerCycleRun {
+                interruptions: vec![TimerInterruption {
+                    kind: TimerInterruptionKind::External,
+                    note: Some("phone call".to_string()),
+                }],
+                ..TimerCycle::new("Work", 900).into()
+            }),
         ] {
             let notification = event.clone().into_notification();
             assert!(notification.is_notification());
@@ -357,10 +1158,11 @@ mod tests {
         // come out in alphabetical order, since Value maps are backed by
         // a BTreeMap. Object member order carries no meaning in JSON, so
         // this is stable rather than canonical.
-        let began = TimerEvent::Began(TimerCycle::new("Work", 1500)).into_notification();
+        let began =
+            TimerEvent::Began(TimerCycle::new("Work", 1_500_000).into()).into_notification();
         assert_eq!(
             serde_json::to_string(&began).unwrap(),
-            r#"{"jsonrpc":"2.0","method":"timer.began","params":{"cycle":{"duration":1500,"name":"Work"}}}"#
+            r#"{"jsonrpc":"2.0","method":"timer.began","params":{"cycle":{"duration":1500,"duration-ms":1500000,"name":"Work"}}}"#
         );
 
         let started = TimerEvent::Started.into_notification();
@@ -368,6 +1170,40 @@ mod tests {
             serde_json::to_string(&started).unwrap(),
             r#"{"jsonrpc":"2.0","method":"timer.started"}"#
         );
+
+        let loop_began = TimerEvent::LoopBegan(2).into_notification();
+        assert_eq!(
+            serde_json::to_string(&loop_began).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"timer.loopBegan","params":{"loop":2}}"#
+        );
+    }
+
+    #[test]
+    fn a_label_travels_beside_the_configured_cycle() {
+        let labeled = TimerCycleRun {
+            task: Some("review PR 42".to_string()),
+            tags: vec!["review".to_string()],
+            ..TimerCycle::new("Work", 1_500_000).into()
+        };
+        let value = serde_json::to_value(&labeled).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "duration": 1500,
+                "duration-ms": 1_500_000,
+                "name": "Work",
+                "tags": ["review"],
+                "task": "review PR 42",
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<TimerCycleRun>(value.clone()).unwrap(),
+            labeled
+        );
+
+        // A configured cycle knows nothing of the label.
+        let configured: TimerCycle = serde_json::from_value(value).unwrap();
+        assert_eq!(configured, TimerCycle::new("Work", 1_500_000));
     }
 
     #[test]
@@ -382,7 +1218,7 @@ mod tests {
         let value = events.to_value();
         assert_eq!(value.to_string(), r#"{"events":[{"event":"started"}]}"#);
         assert_eq!(
-            TimerResponse::from_value(&TimerRequest::Start, value).unwrap(),
+            TimerResponse::from_value(&TimerRequest::Pause, value).unwrap(),
             events
         );
 
@@ -390,22 +1226,34 @@ mod tests {
         let value = subscribed.to_value();
         assert_eq!(value.to_string(), r#"{"subscribed":true}"#);
         assert_eq!(
-            TimerResponse::from_value(&TimerRequest::Subscribe, value).unwrap(),
+            TimerResponse::from_value(
+                &TimerRequest::Subscribe {
+                    filter: TimerSubscribeParams::default()
+                },
+                value
+            )
+            .unwrap(),
             subscribed
         );
     }
 
     #[test]
     fn events_inside_a_result_carry_their_kind() {
-        let value = TimerResponse::Events(vec![
-            TimerEvent::Ended(TimerCycle::new("Work", 0)),
+        let events = TimerResponse::Events(vec![
+            TimerEvent::Ended(TimerCycle::new("Work", 0).into()),
+            TimerEvent::LoopEnded(3),
+            TimerEvent::Completed,
             TimerEvent::Stopped,
-        ])
-        .to_value();
+        ]);
+        let value = events.to_value();
 
         assert_eq!(
             value.to_string(),
-            r#"{"events":[{"cycle":{"duration":0,"name":"Work"},"event":"ended"},{"event":"stopped"}]}"#
+            r#"{"events":[{"cycle":{"duration":0,"duration-ms":0,"name":"Work"},"event":"ended"},{"event":"loopEnded","loop":3},{"event":"completed"},{"event":"stopped"}]}"#
+        );
+        assert_eq!(
+            TimerResponse::from_value(&TimerRequest::Stop, value).unwrap(),
+            events
         );
     }
 
@@ -414,4 +1262,182 @@ mod tests {
         let err = Jsonrpc20Error::method_not_found("timer.nope");
         assert_eq!(err.to_string(), "Unknown method `timer.nope` (code -32601)");
     }
+
+    #[test]
+    fn a_refusal_travels_as_a_server_defined_error() {
+        let err = TimerError::NotRunning.into_jsonrpc(&TimerState::Paused);
+
+        assert!((Jsonrpc20Error::SERVER_MIN..=Jsonrpc20Error::SERVER_MAX).contains(&err.code));
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"code":-32002,"message":"The timer is not running","data":{"error":"notRunning","state":"Paused"}}"#
+        );
+        assert_eq!(TimerError::from_jsonrpc(&err), Some(TimerError::NotRunning));
+        assert_eq!(
+            TimerError::from_jsonrpc(&Jsonrpc20Error::internal("boom")),
+            None
+        );
+    }
+
+    #[test]
+    fn a_refused_parameter_travels_as_invalid_params() {
+        for refusal in [TimerError::OutOfSchedule, TimerError::OutOfRange] {
+            let err = refusal.into_jsonrpc(&TimerState::Running);
+
+            assert_eq!(err.code, Jsonrpc20Error::INVALID_PARAMS);
+            assert_eq!(TimerError::from_jsonrpc(&err), None);
+        }
+    }
+
+    #[test]
+    fn protocol_version_travels_as_major_dot_minor() {
+        let version = TimerProtocolVersion {
+            major: 1,
+            minor: 12,
+        };
+
+        assert_eq!(serde_json::to_string(&version).unwrap(), r#""1.12""#);
+        assert_eq!(
+            serde_json::from_str::<TimerProtocolVersion>(r#""1.12""#).unwrap(),
+            version
+        );
+        assert!(serde_json::from_str::<TimerProtocolVersion>(r#""1""#).is_err());
+        assert!(serde_json::from_str::<TimerProtocolVersion>(r#""1.x""#).is_err());
+    }
+
+    #[test]
+    fn compatibility_follows_the_major_then_the_minor_version() {
+        let client = TimerProtocolVersion { major: 1, minor: 1 };
+        let version = |major, minor| TimerProtocolVersion { major, minor };
+
+        assert_eq!(
+            client.compatibility(&version(1, 1)),
+            TimerCompatibility::Full
+        );
+        assert_eq!(
+            client.compatibility(&version(1, 4)),
+            TimerCompatibility::Full
+        );
+        assert_eq!(
+            client.compatibility(&version(1, 0)),
+            TimerCompatibility::Partial
+        );
+        assert_eq!(
+            client.compatibility(&version(2, 1)),
+            TimerCompatibility::Incompatible
+        );
+        assert_eq!(
+            client.compatibility(&version(0, 9)),
+            TimerCompatibility::Incompatible
+        );
+    }
+
+    #[test]
+    fn server_info_tolerates_members_it_does_not_know() {
+        let value = serde_json::json!({
+            "version": "1.2.0",
+            "protocol": "1.3",
+            "methods": ["timer.get", "timer.later"],
+            "features": ["tcp", "later"],
+            "later": true,
+        });
+
+        let TimerResponse::Info(info) =
+            TimerResponse::from_value(&TimerRequest::Info, value).unwrap()
+        else {
+            panic!("expected server info");
+        };
+
+        assert_eq!(info.protocol, TimerProtocolVersion { major: 1, minor: 3 });
+        assert!(info.supports(TimerRequest::GET));
+        assert!(!info.supports(TimerRequest::SKIP));
+        assert!(info.has(TimerServerInfo::TCP));
+        assert!(!info.has(TimerServerInfo::PERSISTENCE));
+    }
+
+    #[test]
+    fn subscribe_wire_shape_is_stable() {
+        let filter = TimerSubscribeParams {
+            include: TimerEventFilter {
+                events: vec!["began".to_string(), "ended".to_string()],
+                cycles: Vec::new(),
+            },
+            exclude: TimerEventFilter::default(),
+            ticks: None,
+        };
+
+        let line = serde_json::to_string(&TimerRequest::Subscribe { filter }.into_jsonrpc(3));
+        assert_eq!(
+            line.unwrap(),
+            r#"{"jsonrpc":"2.0","method":"timer.subscribe","params":{"include":{"events":["began","ended"]}},"id":3}"#
+        );
+
+        let filter = TimerSubscribeParams::default();
+        let line = serde_json::to_string(&TimerRequest::Subscribe { filter }.into_jsonrpc(4));
+        assert_eq!(
+            line.unwrap(),
+            r#"{"jsonrpc":"2.0","method":"timer.subscribe","id":4}"#
+        );
+    }
+
+    #[test]
+    fn subscribe_with_an_unknown_event_kind_is_invalid_params() {
+        let params = serde_json::json!({ "exclude": { "events": ["timer.running"] } });
+        let request = Jsonrpc20Request::new(TimerRequest::SUBSCRIBE, Some(params), 1);
+
+        let err = TimerRequest::from_jsonrpc(&request).unwrap_err();
+        assert_eq!(err.code, Jsonrpc20Error::INVALID_PARAMS);
+        assert_eq!(err.message, "Unknown event kind `timer.running`");
+    }
+
+    #[test]
+    fn a_filter_includes_then_excludes() {
+        let work = TimerCycle::new("Work", 0);
+        let rest = TimerCycle::new("Break", 0);
+
+        let filter = TimerSubscribeParams {
+            include: TimerEventFilter {
+                events: Vec::new(),
+                cycles: vec!["Work".to_string()],
+            },
+            exclude: TimerEventFilter {
+                events: vec!["running".to_string()],
+                cycles: Vec::new(),
+            },
+            ticks: None,
+        };
+
+        assert!(filter.matches(&TimerEvent::Began(work.clone().into())));
+        assert!(!filter.matches(&TimerEvent::Running(work.into())));
+        assert!(!filter.matches(&TimerEvent::Began(rest.into())));
+        // Cycle lists leave out nothing that concerns no cycle.
+        assert!(filter.matches(&TimerEvent::Started));
+        assert!(filter.matches(&TimerEvent::LoopBegan(1)));
+        assert!(TimerSubscribeParams::default().matches(&TimerEvent::Stopped));
+        assert_eq!(
+            TimerEvent::Set(TimerCycle::new("Work", 0).into()).kind(),
+            "durationSet"
+        );
+    }
+
+    #[test]
+    fn ticks_travel_as_an_interval_or_a_precision() {
+        let every: TimerTicks = serde_json::from_str(r#"{"every":10}"#).unwrap();
+        assert_eq!(every, TimerTicks::Every(10));
+        assert_eq!(every.step(1_499_000), every.step(1_490_000));
+        assert_ne!(every.step(1_490_000), every.step(1_489_000));
+
+        let precision = TimerTicks::Precision(TimerPrecision::Minute);
+        assert_eq!(
+            serde_json::to_string(&precision).unwrap(),
+            r#"{"precision":"minute"}"#
+        );
+        assert_eq!(precision.step(1_499_000), precision.step(1_441_000));
+        assert_ne!(precision.step(1_441_000), precision.step(1_439_000));
+
+        let params = serde_json::json!({ "ticks": { "every": 0 } });
+        let request = Jsonrpc20Request::new(TimerRequest::SUBSCRIBE, Some(params), 1);
+        let err = TimerRequest::from_jsonrpc(&request).unwrap_err();
+        assert_eq!(err.code, Jsonrpc20Error::INVALID_PARAMS);
+    }
 }
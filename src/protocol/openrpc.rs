@@ -0,0 +1,316 @@
+//! The OpenRPC document describing the Comodoro method surface.
+//!
+//! [OpenRPC] is to JSON-RPC what OpenAPI is to REST: one document
+//! naming every method, the schema of its parameters and of its result,
+//! and the errors it answers with, from which a client in any language
+//! can be generated. This one is built from the method lists of
+//! [`crate::protocol`] and from the schemas its types derive, rather
+//! than written by hand, so it cannot drift from what a server answers.
+//!
+//! A server returns it for `rpc.discover`, and `comodoro json-schema`
+//! writes it beside the schemas of the commands.
+//!
+//! OpenRPC has no word for a notification a server pushes, so those are
+//! listed under the `x-notifications` extension, each in the shape of a
+//! method without a result.
+//!
+//! [OpenRPC]: https://spec.open-rpc.org/
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use schemars::{JsonSchema, SchemaGenerator, generate::SchemaSettings};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    jsonrpc20::Jsonrpc20Error,
+    protocol::{
+        TimerCycleParams, TimerEventsResult, TimerExtendParams, TimerGotoParams, TimerLabelParams,
+        TimerLoopParams, TimerRequest, TimerServerInfo, TimerSetParams, TimerSubscribeParams,
+        TimerSubscriptionResult,
+    },
+    timer::{Timer, TimerError, TimerEvent, TimerInterruption},
+};
+
+/// The version of the OpenRPC specification the document follows.
+pub const OPENRPC_VERSION: &str = "1.3.2";
+
+/// The schema of an OpenRPC document, which is what `rpc.discover`
+/// returns.
+const META_SCHEMA: &str =
+    "https://raw.githubusercontent.com/open-rpc/meta-schema/master/schema.json";
+
+/// Every refusal a server answers with, in code order.
+///
+/// A goto out of the schedule and an extension out of range are
+/// answered as invalid parameters rather than as refusals, so neither
+/// is listed.
+const REFUSALS: [TimerError; 5] = [
+    TimerError::Stopped,
+    TimerError::NotRunning,
+    TimerError::AlreadyRunning,
+    TimerError::CountingUp,
+    TimerError::NoTime,
+];
+
+/// The name the invalid parameters error goes by under
+/// `components.errors`.
+const INVALID_PARAMS: &str = "invalidParams";
+
+/// Builds the OpenRPC document of the methods a server answers and of
+/// the notifications it pushes.
+///
+/// The schemas follow JSON Schema draft-07, as OpenRPC does, and every
+/// type they share is defined once under `components.schemas`.
+pub fn document() -> Value {
+    let mut generator = SchemaSettings::draft07()
+        .with(|settings| settings.definitions_path = "/components/schemas".into())
+        .into_generator();
+
+    let methods: Vec<Value> = TimerRequest::METHODS
+        .iter()
+        .map(|method| describe_method(&mut generator, method))
+        .collect();
+
+    let notifications: Vec<Value> = TimerEvent::METHODS
+        .iter()
+        .map(|method| describe_notification(&mut generator, method))
+        .collect();
+
+    let mut errors: Map<String, Value> = REFUSALS
+        .iter()
+        .map(|err| {
+            let error = json!({ "code": err.code(), "message": err.to_string() });
+            (kind(err), error)
+        })
+        .collect();
+
+    errors.insert(
+        INVALID_PARAMS.into(),
+        json!({ "code": Jsonrpc20Error::INVALID_PARAMS, "message": "Invalid params" }),
+    );
+
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": "Comodoro",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "methods": methods,
+        "x-notifications": notifications,
+        "components": {
+            "schemas": generator.take_definitions(true),
+            "errors": errors,
+        },
+    })
+}
+
+fn describe_method(generator: &mut SchemaGenerator, method: &str) -> Value {
+    let params = match method {
+        TimerRequest::START | TimerRequest::LABEL => params::<TimerLabelParams>(generator),
+        TimerRequest::SET => params::<TimerSetParams>(generator),
+        TimerRequest::EXTEND => params::<TimerExtendParams>(generator),
+        TimerRequest::GOTO => params::<TimerGotoParams>(generator),
+        TimerRequest::INTERRUPT => params::<TimerInterruption>(generator),
+        TimerRequest::SUBSCRIBE => params::<TimerSubscribeParams>(generator),
+        _ => Vec::new(),
+    };
+
+    let result = match method {
+        TimerRequest::GET => json!({
+            "name": "timer",
+            "schema": generator.subschema_for::<Timer>(),
+        }),
+        TimerRequest::SUBSCRIBE | TimerRequest::UNSUBSCRIBE => json!({
+            "name": "subscription",
+            "schema": generator.subschema_for::<TimerSubscriptionResult>(),
+        }),
+        TimerRequest::INFO => json!({
+            "name": "info",
+            "schema": generator.subschema_for::<TimerServerInfo>(),
+        }),
+        TimerRequest::DISCOVER => json!({
+            "name": "document",
+            "schema": { "$ref": META_SCHEMA },
+        }),
+        _ => json!({
+            "name": "events",
+            "schema": generator.subschema_for::<TimerEventsResult>(),
+        }),
+    };
+
+    // NOTE: a method taking parameters may find them wrong whatever
+    // the state, which the server answers before any refusal.
+    let errors: Vec<Value> = (!params.is_empty())
+        .then(|| INVALID_PARAMS.to_string())
+        .into_iter()
+        .chain(refusals(method).iter().map(kind))
+        .map(|kind| json!({ "$ref": format!("#/components/errors/{kind}") }))
+        .collect();
+
+    let mut description = json!({
+        "name": method,
+        "paramStructure": "by-name",
+        "params": params,
+        "result": result,
+    });
+
+    if !errors.is_empty() {
+        description["errors"] = Value::Array(errors);
+    }
+
+    description
+}
+
+fn describe_notification(generator: &mut SchemaGenerator, method: &str) -> Value {
+    let params = match method {
+        TimerEvent::STARTED
+        | TimerEvent::STOPPED
+        | TimerEvent::GOAL_REACHED
+        | TimerEvent::COMPLETED => Vec::new(),
+        TimerEvent::LOOP_BEGAN | TimerEvent::LOOP_ENDED => params::<TimerLoopParams>(generator),
+        _ => params::<TimerCycleParams>(generator),
+    };
+
+    json!({
+        "name": method,
+        "paramStructure": "by-name",
+        "params": params,
+    })
+}
+
+/// The refusals the timer may answer `method` with.
+fn refusals(method: &str) -> &'static [TimerError] {
+    use TimerError::*;
+
+    match method {
+        TimerRequest::START => &[AlreadyRunning],
+        TimerRequest::PAUSE => &[Stopped, NotRunning],
+        TimerRequest::RESUME => &[Stopped, AlreadyRunning, NoTime],
+        TimerRequest::STOP => &[Stopped],
+        TimerRequest::SET | TimerRequest::EXTEND => &[Stopped, NotRunning, CountingUp, NoTime],
+        TimerRequest::SKIP => &[Stopped, NoTime],
+        TimerRequest::GOTO => &[NoTime],
+        TimerRequest::INTERRUPT => &[Stopped, NotRunning],
+        _ => &[],
+    }
+}
+
+/// One content descriptor per member of the object `T` describes,
+/// which is how OpenRPC lists parameters passed by name.
+fn params<T: JsonSchema>(generator: &mut SchemaGenerator) -> Vec<Value> {
+    let schema = T::json_schema(generator);
+
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, schema)| {
+            json!({
+                "name": name,
+                "schema": schema,
+                "required": required.contains(&name.as_str()),
+            })
+        })
+        .collect()
+}
+
+/// The name a refusal goes by in `data` and under
+/// `components.errors`.
+fn kind(err: &TimerError) -> String {
+    match json!(err) {
+        Value::String(kind) => kind,
+        _ => unreachable!("a refusal serializes as its name"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use serde_json::Value;
+
+    use crate::{
+        protocol::{TimerRequest, openrpc::document},
+        timer::TimerEvent,
+    };
+
+    /// Every local `$ref` found in `value`.
+    fn refs(value: &Value) -> Vec<&str> {
+        match value {
+            Value::Object(object) => object
+                .iter()
+                .flat_map(|(key, value)| match (key.as_str(), value) {
+                    ("$ref", Value::String(target)) if target.starts_with('#') => {
+                        alloc::vec![target.as_str()]
+                    }
+                    _ => refs(value),
+                })
+                .collect(),
+            Value::Array(values) => values.iter().flat_map(refs).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn the_document_lists_every_method_and_notification() {
+        let document = document();
+
+        let names = |key: &str| -> Vec<&str> {
+            document[key]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|method| method["name"].as_str().unwrap())
+                .collect()
+        };
+
+        assert_eq!(names("methods"), TimerRequest::METHODS);
+        assert_eq!(names("x-notifications"), TimerEvent::METHODS);
+    }
+
+    #[test]
+    fn every_reference_resolves_inside_the_document() {
+        let document = document();
+
+        for target in refs(&document) {
+            let pointer = target.trim_start_matches('#');
+            assert!(document.pointer(pointer).is_some(), "dangling {target}");
+        }
+    }
+
+    #[test]
+    fn parameters_say_which_ones_are_required() {
+        let document = document();
+        let goto = document["methods"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|method| method["name"] == TimerRequest::GOTO)
+            .unwrap();
+
+        let params = goto["params"].as_array().unwrap();
+        assert_eq!(params[0]["name"], "cycle");
+        assert_eq!(params[0]["required"], true);
+        assert_eq!(params[1]["name"], "loop");
+        assert_eq!(params[1]["required"], false);
+        assert_eq!(
+            goto["errors"][0]["$ref"],
+            "#/components/errors/invalidParams"
+        );
+        assert_eq!(goto["errors"][1]["$ref"], "#/components/errors/noTime");
+        assert_eq!(goto["errors"].as_array().unwrap().len(), 2);
+    }
+}
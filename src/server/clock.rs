@@ -0,0 +1,154 @@
+//! Clocks a timer server runs on.
+//!
+//! The timer itself reads no clock: every method takes the time as a
+//! parameter. A server has to read one, and to wait on it between
+//! ticks, which is what [`TimerClock`] abstracts. [`SystemClock`] is
+//! the real one, and [`VirtualClock`] only moves when told to, so a test
+//! can run a whole day of cycles over a real socket in milliseconds.
+
+use alloc::{sync::Arc, vec::Vec};
+
+use std::{
+    sync::{LazyLock, Mutex, MutexGuard},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// A wake-up the server registers with its clock, run whenever the
+/// clock moves without the real time passing.
+pub type TimerWake = Arc<dyn Fn() + Send + Sync>;
+
+/// The time a timer server runs on.
+pub trait TimerClock: Send + Sync {
+    /// The current time, in Unix epoch milliseconds.
+    fn now(&self) -> u64;
+
+    /// Milliseconds since an arbitrary origin, on a clock that nobody
+    /// sets and that stands still while the machine is suspended.
+    ///
+    /// The server compares it with [`Self::now`] to tell the time that
+    /// passed apart from the time the clock jumped.
+    fn monotonic(&self) -> u64;
+
+    /// How long to wait in real time for this clock to move `ms`
+    /// forward, at most.
+    fn timeout(&self, ms: u64) -> Duration;
+
+    /// Registers `wake`, to run whenever this clock moves in a way the
+    /// real time passing does not show.
+    ///
+    /// The real clock never does, hence the default doing nothing.
+    fn register(&self, wake: TimerWake) {
+        let _ = wake;
+    }
+}
+
+/// The clock of the machine the server runs on.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl TimerClock for SystemClock {
+    /// A clock set before the epoch is not a recoverable condition for
+    /// a timer, so it saturates at zero rather than threading an error
+    /// through every call site.
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    fn monotonic(&self) -> u64 {
+        ORIGIN.elapsed().as_millis() as u64
+    }
+
+    fn timeout(&self, ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+}
+
+/// The origin of the monotonic milliseconds of the [`SystemClock`].
+static ORIGIN: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// A clock that moves only when told to.
+///
+/// Clones share the same time, so a test keeps one to drive the clock
+/// of the server it handed the other to.
+#[derive(Clone, Default)]
+pub struct VirtualClock {
+    time: Arc<Mutex<VirtualTime>>,
+}
+
+#[derive(Default)]
+struct VirtualTime {
+    now: u64,
+    monotonic: u64,
+    wakes: Vec<TimerWake>,
+}
+
+impl VirtualClock {
+    /// Creates a clock reading `now`, in Unix epoch milliseconds.
+    pub fn new(now: u64) -> Self {
+        let time = VirtualTime {
+            now,
+            ..Default::default()
+        };
+
+        Self {
+            time: Arc::new(Mutex::new(time)),
+        }
+    }
+
+    /// Moves the clock `ms` forward, as that much time passing would.
+    pub fn advance(&self, ms: u64) {
+        self.update(|time| {
+            time.now += ms;
+            time.monotonic += ms;
+        });
+    }
+
+    /// Sets the clock to `now`, in Unix epoch milliseconds, with no time
+    /// passing, as a machine waking from suspend or a clock set by hand
+    /// would.
+    pub fn set(&self, now: u64) {
+        self.update(|time| time.now = now);
+    }
+
+    /// Applies `f` to the time, then runs the wake-ups.
+    fn update(&self, f: impl FnOnce(&mut VirtualTime)) {
+        // NOTE: a wake-up locks the timer, whose holder may be reading
+        // this clock, so they run once the time is unlocked.
+        let wakes = {
+            let mut time = self.lock();
+            f(&mut time);
+            time.wakes.clone()
+        };
+
+        for wake in wakes {
+            wake();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VirtualTime> {
+        self.time.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl TimerClock for VirtualClock {
+    fn now(&self) -> u64 {
+        self.lock().now
+    }
+
+    fn monotonic(&self) -> u64 {
+        self.lock().monotonic
+    }
+
+    /// Real time never moves this clock, so a wait lasts until a
+    /// wake-up ends it.
+    fn timeout(&self, _ms: u64) -> Duration {
+        Duration::MAX
+    }
+
+    fn register(&self, wake: TimerWake) {
+        self.lock().wakes.push(wake);
+    }
+}
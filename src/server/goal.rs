@@ -0,0 +1,224 @@
+//! Daily goal of a server.
+//!
+//! A [`TimerGoal`] asks a day to hold so many cycles of one name, so
+//! much time of them, or both. The server counts the cycles that ran
+//! out into the [`TimerGoalProgress`] the timer carries, which is how
+//! `timer.get` shows it and the store keeps it, and the count starts
+//! over whenever a new day begins.
+//!
+//! A day begins at a time of day the goal sets, at its offset from
+//! UTC, so a night owl's day can end at 4 in the morning. The goal
+//! performs no I/O and reads no clock, like the recorder it takes its
+//! records from.
+
+use alloc::string::String;
+
+use crate::{
+    server::history::{TimerEnd, TimerRecord},
+    timer::{Timer, TimerEvent, TimerGoalProgress},
+};
+
+/// The milliseconds of a day.
+const DAY: i64 = 86_400_000;
+
+/// What a day should hold.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimerGoal {
+    /// The name of the cycles the goal counts.
+    pub cycle: String,
+    /// How many of those cycles should run out, if the goal counts
+    /// cycles.
+    pub cycles: Option<usize>,
+    /// How many milliseconds they should run, if the goal counts time.
+    pub duration: Option<usize>,
+    /// The milliseconds after midnight UTC at which a day begins,
+    /// negative when it begins the evening before.
+    pub day_start: i64,
+}
+
+impl TimerGoal {
+    /// The Unix epoch milliseconds at which the day `now` falls in
+    /// began.
+    pub fn day(&self, now: u64) -> u64 {
+        let day = (now as i64 - self.day_start).div_euclid(DAY);
+        (day * DAY + self.day_start).max(0) as u64
+    }
+
+    /// Brings the progress `timer` carries to the day `now` falls in.
+    ///
+    /// The count starts over once the day it counted ends, or when it
+    /// counted other cycles. A goal whose targets changed during the
+    /// day keeps its count, and is reached or not as of it, silently.
+    pub fn refresh(&self, timer: &mut Timer, now: u64) {
+        let since_ms = self.day(now);
+
+        let progress = match timer.goal.take() {
+            Some(progress) if progress.cycle == self.cycle && progress.since_ms == since_ms => {
+                progress
+            }
+            _ => TimerGoalProgress {
+                cycle: self.cycle.clone(),
+                since_ms,
+                ..Default::default()
+            },
+        };
+
+        let mut progress = TimerGoalProgress {
+            target_cycles: self.cycles,
+            target_duration_ms: self.duration,
+            ..progress
+        };
+
+        progress.reached = progress.meets_targets();
+        timer.goal = Some(progress);
+    }
+
+    /// Counts `records`, the cycles that ended at `now`, towards the
+    /// progress `timer` carries.
+    ///
+    /// Only the cycles of the goal that ran out, during the current
+    /// day, count. Returns [`TimerEvent::GoalReached`] when they make
+    /// the day reach the goal, which it does once a day.
+    pub fn track(
+        &self,
+        timer: &mut Timer,
+        records: &[TimerRecord],
+        now: u64,
+    ) -> Option<TimerEvent> {
+        self.refresh(timer, now);
+        let progress = timer.goal.as_mut()?;
+
+        for record in records {
+            if record.cycle != self.cycle
+                || record.end != TimerEnd::Completed
+                || record.ended_at_ms < progress.since_ms
+            {
+                continue;
+            }
+
+            progress.cycles += 1;
+            progress.duration_ms += record.actual_ms;
+        }
+
+        if progress.reached || !progress.meets_targets() {
+            return None;
+        }
+
+        progress.reached = true;
+        Some(TimerEvent::GoalReached)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec, vec::Vec};
+
+    use crate::{
+        server::history::{TimerEnd, TimerRecord},
+        timer::{Timer, TimerCycle, TimerEvent, TimerSchedule},
+    };
+
+    use super::TimerGoal;
+
+    /// 2026-10-12T00:00:00Z.
+    const MIDNIGHT: u64 = 1_791_763_200_000;
+    const HOUR: u64 = 3_600_000;
+
+    /// Two Work cycles, 04:00 at +02:00 beginning the day.
+    fn goal() -> TimerGoal {
+        TimerGoal {
+            cycle: "Work".to_string(),
+            cycles: Some(2),
+            duration: None,
+            day_start: 2 * HOUR as i64,
+        }
+    }
+
+    fn timer() -> Timer {
+        Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("Work", 1_500_000)],
+            ..Default::default()
+        })
+    }
+
+    fn record(cycle: &str, ended_at_ms: u64, end: TimerEnd) -> TimerRecord {
+        TimerRecord {
+            cycle: cycle.to_string(),
+            began_at_ms: ended_at_ms - 1_500_000,
+            ended_at_ms,
+            planned_ms: Some(1_500_000),
+            actual_ms: 1_500_000,
+            pauses: 0,
+            paused_ms: 0,
+            end,
+            task: None,
+            tags: vec![],
+            interruptions: vec![],
+        }
+    }
+
+    #[test]
+    fn a_goal_is_reached_once_a_day() {
+        let (goal, mut timer) = (goal(), timer());
+        let now = MIDNIGHT + 9 * HOUR;
+
+        let records = [
+            record("Work", now, TimerEnd::Completed),
+            record("Work", now, TimerEnd::Skipped),
+            record("Rest", now, TimerEnd::Completed),
+        ];
+        assert_eq!(goal.track(&mut timer, &records, now), None);
+
+        let progress = timer.goal.as_ref().unwrap();
+        assert_eq!(progress.since_ms, MIDNIGHT + 2 * HOUR);
+        assert_eq!((progress.cycles, progress.duration_ms), (1, 1_500_000));
+
+        let records = [record("Work", now + HOUR, TimerEnd::Completed)];
+        let reached = goal.track(&mut timer, &records, now + HOUR);
+        assert_eq!(reached, Some(TimerEvent::GoalReached));
+
+        let records = [record("Work", now + 2 * HOUR, TimerEnd::Completed)];
+        assert_eq!(goal.track(&mut timer, &records, now + 2 * HOUR), None);
+        assert_eq!(timer.goal.as_ref().unwrap().cycles, 3);
+    }
+
+    #[test]
+    fn the_count_starts_over_when_the_day_begins() {
+        let (goal, mut timer) = (goal(), timer());
+
+        // 03:30 at +02:00 still belongs to the day before.
+        let late = MIDNIGHT + HOUR + HOUR / 2;
+        let records = [record("Work", late, TimerEnd::Completed)];
+        goal.track(&mut timer, &records, late);
+        assert_eq!(
+            timer.goal.as_ref().unwrap().since_ms,
+            MIDNIGHT + 2 * HOUR - 86_400_000
+        );
+
+        goal.refresh(&mut timer, MIDNIGHT + 2 * HOUR);
+        let progress = timer.goal.as_ref().unwrap();
+        assert_eq!(progress.since_ms, MIDNIGHT + 2 * HOUR);
+        assert_eq!(progress.cycles, 0);
+    }
+
+    #[test]
+    fn a_goal_counting_time_adds_up_the_cycles() {
+        let goal = TimerGoal {
+            cycles: None,
+            duration: Some(2_500_000),
+            ..goal()
+        };
+        let mut timer = timer();
+        let now = MIDNIGHT + 9 * HOUR;
+
+        let reached: Vec<_> = (0..2)
+            .filter_map(|i| {
+                let records = [record("Work", now + i * HOUR, TimerEnd::Completed)];
+                goal.track(&mut timer, &records, now + i * HOUR)
+            })
+            .collect();
+
+        assert_eq!(reached, vec![TimerEvent::GoalReached]);
+        assert_eq!(timer.goal.unwrap().duration_ms, 3_000_000);
+    }
+}
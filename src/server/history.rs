@@ -0,0 +1,280 @@
+//! History of the cycles a server ran.
+//!
+//! A server forgets a cycle once it ends, so a [`TimerRecorder`]
+//! follows the events the timer emits and turns every cycle that ends
+//! into a [`TimerRecord`]: when it began and ended, how long it was
+//! meant to last and actually lasted, how often and how long it was
+//! paused, what interrupted it, and how it ended. A [`TimerHistory`]
+//! appends those records to a JSON Lines file, one record per line,
+//! and reads them back.
+//!
+//! The recorder performs no I/O and reads no clock, like the timer, so
+//! it works without the server as well as within it.
+
+use alloc::{format, string::String, vec::Vec};
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufRead, BufReader, ErrorKind, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::timer::{TimerEvent, TimerInterruption};
+
+/// One cycle the timer ran.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TimerRecord {
+    /// The name of the cycle.
+    pub cycle: String,
+    /// Unix epoch milliseconds at which the cycle began.
+    pub began_at_ms: u64,
+    /// Unix epoch milliseconds at which the cycle ended.
+    pub ended_at_ms: u64,
+    /// The milliseconds the cycle had when it began, absent for a cycle
+    /// counting up, which has none.
+    pub planned_ms: Option<usize>,
+    /// The milliseconds the cycle ran, its pauses left out.
+    pub actual_ms: usize,
+    /// How many times the cycle was paused.
+    pub pauses: usize,
+    /// The milliseconds the cycle spent paused.
+    pub paused_ms: usize,
+    /// How the cycle ended.
+    pub end: TimerEnd,
+    /// The task the timer ran the cycle for, as last labeled during it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// The tags of that task.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The interruptions recorded during the cycle, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interruptions: Vec<TimerInterruption>,
+}
+
+/// How a cycle ended.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimerEnd {
+    /// The cycle ran out, however it was acknowledged afterwards.
+    Completed,
+    /// A client skipped the rest of the cycle.
+    Skipped,
+    /// A client stopped the timer during the cycle.
+    Stopped,
+    /// A client moved the timer to another cycle.
+    Moved,
+}
+
+/// Turns the events a timer emits into the records of the cycles that
+/// ended.
+#[derive(Debug, Default)]
+pub struct TimerRecorder {
+    /// The cycle in progress, if the recorder saw it begin.
+    open: Option<TimerOpenRecord>,
+    /// Whether the timer is paused.
+    paused: bool,
+}
+
+/// A cycle in progress.
+#[derive(Debug)]
+struct TimerOpenRecord {
+    cycle: String,
+    began_at: u64,
+    planned: Option<usize>,
+    pauses: usize,
+    paused: usize,
+    paused_at: Option<u64>,
+    /// Whether the cycle already ran out, and counts overtime.
+    completed: bool,
+    task: Option<String>,
+    tags: Vec<String>,
+    interruptions: Vec<TimerInterruption>,
+}
+
+impl TimerOpenRecord {
+    fn close(mut self, now: u64, end: TimerEnd) -> TimerRecord {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused += now.saturating_sub(paused_at) as usize;
+        }
+
+        let lasted = now.saturating_sub(self.began_at) as usize;
+
+        TimerRecord {
+            cycle: self.cycle,
+            began_at_ms: self.began_at,
+            ended_at_ms: now,
+            planned_ms: self.planned,
+            actual_ms: lasted.saturating_sub(self.paused),
+            pauses: self.pauses,
+            paused_ms: self.paused,
+            end: if self.completed {
+                TimerEnd::Completed
+            } else {
+                end
+            },
+            task: self.task,
+            tags: self.tags,
+            interruptions: self.interruptions,
+        }
+    }
+}
+
+impl TimerRecorder {
+    /// Follows `events`, which the timer emitted at `now`, in Unix epoch
+    /// milliseconds, and returns the records of the cycles they ended.
+    ///
+    /// `end` is how the cycle they end ended, unless it ran out: the
+    /// recorder cannot tell a skip from a stop from the events alone,
+    /// only whoever made the timer emit them can.
+    ///
+    /// A cycle ending into overtime stays open until the overtime is
+    /// acknowledged, so the record counts the time it really lasted.
+    /// A cycle the recorder did not see begin, such as one in progress
+    /// when the recorder was created, is left out.
+    pub fn record(&mut self, events: &[TimerEvent], now: u64, end: TimerEnd) -> Vec<TimerRecord> {
+        let mut records = Vec::new();
+        let mut events = events.iter().peekable();
+
+        while let Some(event) = events.next() {
+            match event {
+                TimerEvent::Began(cycle) => {
+                    if let Some(open) = self.open.take() {
+                        records.push(open.close(now, end));
+                    }
+
+                    self.open = Some(TimerOpenRecord {
+                        cycle: cycle.name.clone(),
+                        began_at: now,
+                        planned: (!cycle.count_up).then_some(cycle.duration),
+                        pauses: 0,
+                        paused: 0,
+                        paused_at: self.paused.then_some(now),
+                        completed: false,
+                        task: cycle.task.clone(),
+                        tags: cycle.tags.clone(),
+                        interruptions: Vec::new(),
+                    });
+                }
+                TimerEvent::Paused(_) => {
+                    self.paused = true;
+
+                    if let Some(open) = &mut self.open {
+                        open.pauses += 1;
+                        open.paused_at = Some(now);
+                    }
+                }
+                TimerEvent::Resumed(_) => {
+                    self.paused = false;
+
+                    if let Some(open) = &mut self.open
+                        && let Some(paused_at) = open.paused_at.take()
+                    {
+                        open.paused += now.saturating_sub(paused_at) as usize;
+                    }
+                }
+                TimerEvent::Ended(_) => {
+                    let overtime = matches!(events.peek(), Some(TimerEvent::Overtime(_)));
+
+                    match self.open.take() {
+                        Some(mut open) if overtime => {
+                            open.completed = true;
+                            self.open = Some(open);
+                        }
+                        Some(open) => records.push(open.close(now, end)),
+                        None => (),
+                    }
+                }
+                TimerEvent::Labeled(cycle) => {
+                    if let Some(open) = &mut self.open {
+                        open.task = cycle.task.clone();
+                        open.tags = cycle.tags.clone();
+                    }
+                }
+                TimerEvent::Interrupted(cycle) => {
+                    if let Some(open) = &mut self.open {
+                        open.interruptions = cycle.interruptions.clone();
+                    }
+                }
+                TimerEvent::Stopped => {
+                    self.paused = false;
+
+                    if let Some(open) = self.open.take() {
+                        records.push(open.close(now, end));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        records
+    }
+}
+
+/// The JSON Lines file a server appends its records to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimerHistory {
+    /// Where the records are appended.
+    pub path: PathBuf,
+}
+
+impl TimerHistory {
+    /// Appends `records` to the history, one line each.
+    pub fn append(&self, records: &[TimerRecord]) -> Result<()> {
+        let path = self.path.display();
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Create history directory {} error", dir.display()))?;
+        }
+
+        let mut lines = String::new();
+
+        for record in records {
+            lines.push_str(&serde_json::to_string(record).context("Serialize record error")?);
+            lines.push('\n');
+        }
+
+        // NOTE: the lines go out in one write, so a reader never meets
+        // half of a batch.
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .with_context(|| format!("Append to history {path} error"))
+    }
+
+    /// Reads every record of the history, oldest first, and none when
+    /// there is no history yet.
+    pub fn read(&self) -> Result<Vec<TimerRecord>> {
+        let path = self.path.display();
+
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err).with_context(|| format!("Open history {path} error")),
+        };
+
+        let mut records = Vec::new();
+
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.with_context(|| format!("Read history {path} error"))?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record = serde_json::from_str(&line)
+                .with_context(|| format!("Parse line {} of history {path} error", index + 1))?;
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+}
@@ -1,3 +1,7 @@
 //! Timer server, owning the timer behind its listeners.
 
+pub mod clock;
+pub mod goal;
+pub mod history;
 pub mod std;
+pub mod store;
@@ -4,35 +4,46 @@
 //! asynchronous server would be a sibling rather than a replacement.
 //!
 //! One thread accepts connections per bound address, one thread ticks
-//! the timer every second, and every connection gets a reader thread and
-//! a writer thread. The split matters: a subscribed connection blocks
-//! forever on its next request, so notifications could not be delivered
-//! from the thread that reads them. Every line leaving the server
-//! therefore goes through one channel per connection, which also
-//! serializes writes without locking the socket.
+//! the timer when it next has something to report, and every connection
+//! gets a reader thread and a writer thread. The split matters: a
+//! subscribed connection blocks forever on its next request, so
+//! notifications could not be delivered from the thread that reads
+//! them. Every line leaving the server therefore goes through one
+//! channel per connection, which also serializes writes without locking
+//! the socket.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 use std::{
     io::{BufRead, BufReader, Write},
+    mem,
     sync::{
-        Arc, Mutex, MutexGuard,
+        Arc, Condvar, Mutex, MutexGuard,
         atomic::{AtomicUsize, Ordering},
         mpsc::{self, Receiver, Sender},
     },
     thread,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::Duration,
 };
 
 use anyhow::{Context, Result};
 use log::{debug, error, trace, warn};
+use serde_json::json;
 
 use crate::{
     jsonrpc20::{
         Jsonrpc20Error, Jsonrpc20Incoming, Jsonrpc20Outgoing, Jsonrpc20Request, Jsonrpc20Response,
     },
-    protocol::{TimerRequest, TimerResponse},
-    timer::{Timer, TimerEvent, TimerSchedule},
+    protocol::{
+        TimerProtocolVersion, TimerRequest, TimerResponse, TimerServerInfo, TimerSubscribeParams,
+    },
+    server::{
+        clock::{SystemClock, TimerClock},
+        goal::TimerGoal,
+        history::{TimerEnd, TimerHistory, TimerRecorder},
+        store::TimerStore,
+    },
+    timer::{Timer, TimerCycleRun, TimerError, TimerEvent, TimerJump, TimerSchedule, TimerState},
     transport::{TimerAddress, TimerListener, TimerStream},
 };
 
@@ -51,9 +62,54 @@ pub struct TimerServer {
     pub schedule: TimerSchedule,
     /// The addresses to bind, one listener each.
     pub addresses: Vec<TimerAddress>,
+    /// Whether the timer ticks every second even with no connection
+    /// subscribed, for the receiver [`serve`] returns.
+    ///
+    /// Without it, and without subscribers, the timer only wakes at the
+    /// end of its cycles, since nobody would hear the ticks between.
+    ///
+    /// [`serve`]: TimerServer::serve
+    pub ticks: bool,
+    /// What the timer does with the time its clock jumps over, such as
+    /// the time the machine spends suspended.
+    pub jump: TimerJump,
+    /// The clock the timer runs on, usually a [`SystemClock`].
+    pub clock: Arc<dyn TimerClock>,
+    /// Where the timer is kept across restarts, if anywhere.
+    ///
+    /// The server takes the timer back from there when it starts, and
+    /// saves it there whenever it changes.
+    pub store: Option<TimerStore>,
+    /// Where the cycles the timer ran are recorded, if anywhere.
+    pub history: Option<TimerHistory>,
+    /// What a day should hold, if anything.
+    ///
+    /// The timer carries the progress towards it, counted from the
+    /// cycles that end. A server starting without a progress for the
+    /// current day counts it from the history, if it has one.
+    pub goal: Option<TimerGoal>,
 }
 
 impl TimerServer {
+    /// Creates a server running `schedule` on `addresses`, on the
+    /// system clock.
+    ///
+    /// The timer only ticks for its subscribers, counts the time its
+    /// clock jumps over, and is neither kept, recorded nor given a
+    /// goal. Set the fields to change any of it.
+    pub fn new(schedule: TimerSchedule, addresses: Vec<TimerAddress>) -> Self {
+        Self {
+            schedule,
+            addresses,
+            ticks: false,
+            jump: TimerJump::default(),
+            clock: Arc::new(SystemClock),
+            store: None,
+            history: None,
+            goal: None,
+        }
+    }
+
     /// Binds every address and spawns the accept and tick threads.
     ///
     /// Returns the receiver carrying every event the timer emits, in
@@ -68,14 +124,53 @@ impl TimerServer {
             .map(TimerListener::bind)
             .collect::<Result<Vec<_>>>()?;
 
-        let timer = Arc::new(Mutex::new(Timer::new(self.schedule)));
+        let now = self.clock.now();
+        let info = self.info();
+
+        // NOTE: a store that cannot be read is no reason to refuse
+        // serving, and the first change overwrites it anyway.
+        let restored = self.store.as_ref().and_then(|store| {
+            store.load(&self.schedule, now).unwrap_or_else(|err| {
+                warn!("cannot restore timer: {err:#}");
+                None
+            })
+        });
+
+        let mut timer = restored.unwrap_or_else(|| Timer::new(self.schedule));
+
+        match &self.goal {
+            Some(goal) => count_goal(goal, &mut timer, self.history.as_ref(), now),
+            None => timer.goal = None,
+        }
+
         let (tx, rx) = mpsc::channel();
-        let broadcast = TimerBroadcast::new(tx);
+
+        let host = Arc::new(TimerHost {
+            info,
+            timer: Arc::new(Mutex::new(timer)),
+            broadcast: TimerBroadcast::new(tx),
+            watch: TimerWatch::new(self.clock, self.jump),
+            store: self.store,
+            history: self.history,
+            recorder: Mutex::new(TimerRecorder::default()),
+            goal: self.goal,
+        });
+
+        // NOTE: the tick thread counts on the wake landing while it
+        // waits, rather than between its reading of the clock and its
+        // wait, which taking the timer lock first guarantees.
+        host.watch.clock.register(Arc::new({
+            let timer = host.timer.clone();
+            let wake = host.broadcast.wake.clone();
+            move || {
+                let _timer = lock(&timer);
+                wake.notify_all();
+            }
+        }));
 
         for listener in listeners {
             thread::spawn({
-                let timer = timer.clone();
-                let broadcast = broadcast.clone();
+                let host = host.clone();
                 move || {
                     loop {
                         let stream = match listener.accept() {
@@ -86,11 +181,10 @@ impl TimerServer {
                             }
                         };
 
-                        let timer = timer.clone();
-                        let broadcast = broadcast.clone();
+                        let host = host.clone();
 
                         thread::spawn(move || {
-                            if let Err(err) = serve_connection(stream, timer, broadcast) {
+                            if let Err(err) = serve_connection(stream, &host) {
                                 error!("cannot serve connection: {err}");
                             }
                         });
@@ -100,69 +194,351 @@ impl TimerServer {
         }
 
         thread::spawn({
-            let timer = timer.clone();
-            let broadcast = broadcast.clone();
+            let ticks = self.ticks;
             move || {
                 loop {
-                    thread::sleep(Duration::from_secs(1));
-                    let events: Vec<_> = lock(&timer).update(now()).into_iter().collect();
-                    broadcast.emit(events);
+                    let events = tick(&host, ticks);
+                    host.broadcast.emit(events);
                 }
             }
         });
 
         Ok(rx)
     }
+
+    /// What this server answers [`TimerRequest::Info`] with.
+    fn info(&self) -> TimerServerInfo {
+        let mut features = Vec::new();
+
+        if (self.addresses.iter()).any(|address| matches!(address, TimerAddress::UnixSocket(_))) {
+            features.push(TimerServerInfo::SOCKET);
+        }
+
+        if (self.addresses.iter()).any(|address| matches!(address, TimerAddress::Tcp { .. })) {
+            features.push(TimerServerInfo::TCP);
+        }
+
+        if self.store.is_some() {
+            features.push(TimerServerInfo::PERSISTENCE);
+        }
+
+        if self.history.is_some() {
+            features.push(TimerServerInfo::HISTORY);
+        }
+
+        if self.goal.is_some() {
+            features.push(TimerServerInfo::GOAL);
+        }
+
+        TimerServerInfo {
+            version: String::from(env!("CARGO_PKG_VERSION")),
+            protocol: TimerProtocolVersion::CURRENT,
+            methods: TimerRequest::METHODS
+                .iter()
+                .copied()
+                .map(String::from)
+                .collect(),
+            features: features.into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+/// What every thread of a server shares.
+struct TimerHost {
+    info: TimerServerInfo,
+    timer: Arc<Mutex<Timer>>,
+    broadcast: TimerBroadcast,
+    watch: TimerWatch,
+    store: Option<TimerStore>,
+    history: Option<TimerHistory>,
+    recorder: Mutex<TimerRecorder>,
+    goal: Option<TimerGoal>,
+}
+
+impl TimerHost {
+    /// Keeps what `events`, which `timer` emitted at `now`, changed: the
+    /// cycles they ended, as `end` says, in the goal progress and in the
+    /// history, and the timer in the store.
+    ///
+    /// Appends [`TimerEvent::GoalReached`] to `events` when the cycles
+    /// they ended reach the goal.
+    ///
+    /// The duration the timer shows moves with the clock, and is
+    /// recomputed from the elapsed time once the timer is restored, so
+    /// the periodic ticks carrying it are not worth a write each. A
+    /// failing write costs the next restart its timer or the history a
+    /// record, not this server its request, so it is only logged.
+    fn keep(&self, timer: &mut Timer, events: &mut Vec<TimerEvent>, now: u64, end: TimerEnd) {
+        let changed = events
+            .iter()
+            .any(|event| !matches!(event, TimerEvent::Running(_) | TimerEvent::Overtime(_)));
+
+        if !changed {
+            return;
+        }
+
+        let records = lock(&self.recorder).record(events, now, end);
+
+        if let Some(goal) = &self.goal {
+            events.extend(goal.track(timer, &records, now));
+        }
+
+        if let Some(store) = &self.store
+            && let Err(err) = store.save(timer, now)
+        {
+            error!("cannot save timer: {err:#}");
+        }
+
+        if let Some(history) = &self.history
+            && !records.is_empty()
+            && let Err(err) = history.append(&records)
+        {
+            error!("cannot record cycles: {err:#}");
+        }
+    }
+}
+
+/// Brings the goal progress of `timer` to the day `now` falls in.
+///
+/// A timer restored with no count for that day, such as one saved the
+/// day before or no timer at all, counts it from the cycles `history`
+/// recorded, so a restart in the middle of the day loses nothing. The
+/// goal reached then is not announced: it was when it happened.
+fn count_goal(goal: &TimerGoal, timer: &mut Timer, history: Option<&TimerHistory>, now: u64) {
+    let counted = timer
+        .goal
+        .as_ref()
+        .is_some_and(|progress| progress.cycle == goal.cycle && progress.since_ms == goal.day(now));
+
+    goal.refresh(timer, now);
+
+    let Some(history) = history.filter(|_| !counted) else {
+        return;
+    };
+
+    match history.read() {
+        Ok(records) => {
+            let _ = goal.track(timer, &records, now);
+        }
+        Err(err) => warn!("cannot count goal from history: {err:#}"),
+    }
+}
+
+/// Waits until the timer has something to report, then updates it.
+///
+/// Sleeps until the [`Timer::deadline`], with ticks every second when
+/// `ticks` asks for them or a subscribed connection takes them for the
+/// current cycle. Anything changing
+/// the timer or its subscribers goes through the broadcast, which wakes
+/// the wait so the deadline is computed again.
+///
+/// A jump of the clock found on waking is reported as the watch says,
+/// instead of the update.
+fn tick(host: &TimerHost, ticks: bool) -> Vec<TimerEvent> {
+    let mut timer = lock(&host.timer);
+
+    loop {
+        let (now, mut jumped) = host.watch.read(&mut timer);
+
+        if !jumped.is_empty() {
+            host.keep(&mut timer, &mut jumped, now, TimerEnd::Completed);
+            return jumped;
+        }
+
+        let ticks = ticks || host.broadcast.wants_ticks(&timer.cycle);
+
+        // NOTE: the wait runs on a monotonic clock, which a suspended
+        // machine stops, so it never outlasts a few seconds of it for a
+        // deadline that the wall clock may have passed meanwhile.
+        let timeout = match timer.deadline(now, ticks) {
+            Some(deadline) if deadline <= now => {
+                let mut events: Vec<_> = timer.update(now).into_iter().collect();
+                host.keep(&mut timer, &mut events, now, TimerEnd::Completed);
+                return events;
+            }
+            Some(deadline) => host.watch.clock.timeout(deadline - now).min(MAX_WAIT),
+            None => MAX_WAIT,
+        };
+
+        timer = match host.broadcast.wake.wait_timeout(timer, timeout) {
+            Ok((timer, _)) => timer,
+            Err(err) => {
+                warn!("recovering from a poisoned lock");
+                err.into_inner().0
+            }
+        };
+    }
+}
+
+/// The longest the tick thread waits without looking at the clock.
+const MAX_WAIT: Duration = Duration::from_secs(10);
+
+/// Tells the time that passed apart from the time the clock jumped.
+///
+/// The wall clock is what the timer counts in, and it moves on while
+/// the machine is suspended, or wherever someone sets it. The monotonic
+/// clock does neither, so what the wall clock measured beyond it since
+/// the last reading is a jump.
+struct TimerWatch {
+    clock: Arc<dyn TimerClock>,
+    jump: TimerJump,
+    last: Mutex<(u64, u64)>,
+}
+
+impl TimerWatch {
+    fn new(clock: Arc<dyn TimerClock>, jump: TimerJump) -> Self {
+        let last = (clock.now(), clock.monotonic());
+
+        Self {
+            clock,
+            jump,
+            last: Mutex::new(last),
+        }
+    }
+
+    /// Reads the clock, bringing `timer` across any jump it made since
+    /// the last reading, and returns the time with the events the jump
+    /// emitted.
+    ///
+    /// Meant to be called with the timer locked, so no request sees the
+    /// timer before the jump is dealt with.
+    fn read(&self, timer: &mut Timer) -> (u64, Vec<TimerEvent>) {
+        let (now, monotonic) = (self.clock.now(), self.clock.monotonic());
+        let (wall, then) = mem::replace(&mut *lock(&self.last), (now, monotonic));
+        let from = wall.saturating_add(monotonic.saturating_sub(then));
+
+        if now.abs_diff(from) <= MAX_DRIFT.as_millis() as u64 {
+            return (now, Vec::new());
+        }
+
+        debug!("clock jumped from {from} to {now}");
+        (now, timer.jump(from, now, self.jump).into_iter().collect())
+    }
 }
 
+/// The most the wall clock may drift from the monotonic one between
+/// two readings before it counts as a jump.
+const MAX_DRIFT: Duration = Duration::from_secs(1);
+
 /// Fan-out of the events a timer emits.
 ///
 /// Holds the sender feeding the caller of [`TimerServer::serve`] plus
-/// the line sender of every subscribed connection. A subscriber whose
+/// the line sender of every subscribed connection, with the filter it
+/// subscribed with and the last tick it was sent. A subscriber whose
 /// channel is closed has gone away and is dropped on the next emit, so
 /// nothing has to reap them.
+///
+/// Every change to the timer is emitted through here, so it is also
+/// what wakes the tick thread to compute its deadline again.
 #[derive(Clone)]
 struct TimerBroadcast {
     events: Sender<TimerEvent>,
     subscribers: TimerSubscribers,
+    wake: Arc<Condvar>,
 }
 
-/// The line sender of every subscribed connection, by connection id.
-type TimerSubscribers = Arc<Mutex<Vec<(usize, Sender<String>)>>>;
+/// Every subscribed connection.
+type TimerSubscribers = Arc<Mutex<Vec<TimerSubscriber>>>;
+
+/// A subscribed connection, and the notifications it asked for.
+struct TimerSubscriber {
+    id: usize,
+    lines: Sender<String>,
+    filter: TimerSubscribeParams,
+    /// The kind, the cycle name and the step of the last tick sent,
+    /// when the subscriber asked for fewer ticks.
+    tick: Option<(&'static str, String, usize)>,
+}
+
+impl TimerSubscriber {
+    /// Whether the subscriber takes `event`, keeping it as the last
+    /// tick sent when it is one.
+    ///
+    /// A tick falling in the same step of the same cycle as the last
+    /// one sent is dropped, since the subscriber asked not to be told
+    /// twice.
+    fn takes(&mut self, event: &TimerEvent) -> bool {
+        if !self.filter.matches(event) {
+            return false;
+        }
+
+        let (Some(ticks), TimerEvent::Running(cycle) | TimerEvent::Overtime(cycle)) =
+            (&self.filter.ticks, event)
+        else {
+            return true;
+        };
+
+        let tick = (event.kind(), cycle.name.clone(), ticks.step(cycle.duration));
+
+        if self.tick.as_ref() == Some(&tick) {
+            return false;
+        }
+
+        self.tick = Some(tick);
+        true
+    }
+}
 
 impl TimerBroadcast {
     fn new(events: Sender<TimerEvent>) -> Self {
         Self {
             events,
             subscribers: Arc::new(Mutex::new(Vec::new())),
+            wake: Arc::new(Condvar::new()),
         }
     }
 
-    /// Registers connection `id` as a subscriber, replacing any earlier
-    /// subscription it held so subscribing twice is idempotent.
-    fn subscribe(&self, id: usize, lines: Sender<String>) {
+    /// Whether a subscribed connection takes the ticks of `cycle`.
+    ///
+    /// When none does, the tick thread sleeps until the next transition
+    /// rather than waking every second for nobody.
+    fn wants_ticks(&self, cycle: &TimerCycleRun) -> bool {
+        let running = TimerEvent::Running(cycle.clone());
+        let overtime = TimerEvent::Overtime(cycle.clone());
+
+        lock(&self.subscribers).iter().any(|subscriber| {
+            subscriber.filter.matches(&running) || subscriber.filter.matches(&overtime)
+        })
+    }
+
+    /// Registers connection `id` as a subscriber to the notifications
+    /// `filter` lets through, replacing any earlier subscription it held
+    /// so subscribing twice is idempotent.
+    fn subscribe(&self, id: usize, lines: Sender<String>, filter: TimerSubscribeParams) {
         let mut subscribers = lock(&self.subscribers);
-        subscribers.retain(|(other, _)| *other != id);
-        subscribers.push((id, lines));
+        subscribers.retain(|subscriber| subscriber.id != id);
+        subscribers.push(TimerSubscriber {
+            id,
+            lines,
+            filter,
+            tick: None,
+        });
         debug!("connection {id} subscribed, {} total", subscribers.len());
+        self.wake.notify_all();
     }
 
     /// Removes the subscription of connection `id`, if it holds one.
     fn unsubscribe(&self, id: usize) {
         let mut subscribers = lock(&self.subscribers);
-        subscribers.retain(|(other, _)| *other != id);
+        subscribers.retain(|subscriber| subscriber.id != id);
         debug!("connection {id} unsubscribed, {} left", subscribers.len());
+        self.wake.notify_all();
     }
 
-    /// Sends `events` to the hook receiver and to every subscriber.
+    /// Sends `events` to the hook receiver and to every subscriber
+    /// taking them, as its filter and its ticks say.
     fn emit(&self, events: impl IntoIterator<Item = TimerEvent>) {
+        self.wake.notify_all();
+
         for event in events {
             trace!("emit {event:?}");
 
             match serde_json::to_string(&event.clone().into_notification()) {
                 Ok(line) => {
                     let mut subscribers = lock(&self.subscribers);
-                    subscribers.retain(|(_, lines)| lines.send(line.clone()).is_ok());
+                    subscribers.retain_mut(|subscriber| {
+                        !subscriber.takes(&event) || subscriber.lines.send(line.clone()).is_ok()
+                    });
                 }
                 Err(err) => error!("cannot serialize notification: {err}"),
             }
@@ -175,11 +551,7 @@ impl TimerBroadcast {
 }
 
 /// Serves one connection until the peer disconnects.
-fn serve_connection(
-    stream: TimerStream,
-    timer: Arc<Mutex<Timer>>,
-    broadcast: TimerBroadcast,
-) -> Result<()> {
+fn serve_connection(stream: TimerStream, host: &TimerHost) -> Result<()> {
     let id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
     debug!("begin connection {id}");
 
@@ -210,7 +582,7 @@ fn serve_connection(
 
         trace!("recv: {line}");
 
-        let Some(response) = handle_line(&line, id, &timer, &broadcast, &lines) else {
+        let Some(response) = handle_line(&line, id, host, &lines) else {
             continue;
         };
 
@@ -224,7 +596,7 @@ fn serve_connection(
         }
     }
 
-    broadcast.unsubscribe(id);
+    host.broadcast.unsubscribe(id);
     drop(lines);
     let _ = writer.join();
 
@@ -237,8 +609,7 @@ fn serve_connection(
 fn handle_line(
     line: &str,
     id: usize,
-    timer: &Arc<Mutex<Timer>>,
-    broadcast: &TimerBroadcast,
+    host: &TimerHost,
     lines: &Sender<String>,
 ) -> Option<Jsonrpc20Outgoing> {
     let incoming: Jsonrpc20Incoming = match serde_json::from_str(line) {
@@ -253,7 +624,7 @@ fn handle_line(
 
     match incoming {
         Jsonrpc20Incoming::Single(request) => {
-            handle_request(request, id, timer, broadcast, lines).map(Jsonrpc20Outgoing::Single)
+            handle_request(request, id, host, lines).map(Jsonrpc20Outgoing::Single)
         }
         Jsonrpc20Incoming::Batch(requests) if requests.is_empty() => {
             let err = Jsonrpc20Error::invalid_request("A batch must not be empty");
@@ -264,7 +635,7 @@ fn handle_line(
         Jsonrpc20Incoming::Batch(requests) => {
             let responses: Vec<_> = requests
                 .into_iter()
-                .filter_map(|request| handle_request(request, id, timer, broadcast, lines))
+                .filter_map(|request| handle_request(request, id, host, lines))
                 .collect();
 
             (!responses.is_empty()).then_some(Jsonrpc20Outgoing::Batch(responses))
@@ -276,12 +647,11 @@ fn handle_line(
 fn handle_request(
     request: Jsonrpc20Request,
     id: usize,
-    timer: &Arc<Mutex<Timer>>,
-    broadcast: &TimerBroadcast,
+    host: &TimerHost,
     lines: &Sender<String>,
 ) -> Option<Jsonrpc20Response> {
-    let outcome = TimerRequest::from_jsonrpc(&request)
-        .map(|parsed| dispatch(parsed, id, timer, broadcast, lines));
+    let outcome =
+        TimerRequest::from_jsonrpc(&request).and_then(|parsed| dispatch(parsed, id, host, lines));
 
     if request.is_notification() {
         if let Err(err) = outcome {
@@ -297,44 +667,128 @@ fn handle_request(
 }
 
 /// Applies one parsed request to the timer.
+///
+/// Fails when the parameters name something the timer does not hold,
+/// which only the timer can tell.
 fn dispatch(
     request: TimerRequest,
     id: usize,
-    timer: &Arc<Mutex<Timer>>,
-    broadcast: &TimerBroadcast,
+    host: &TimerHost,
     lines: &Sender<String>,
-) -> TimerResponse {
-    let events: Vec<TimerEvent> = match request {
-        TimerRequest::Get => return TimerResponse::Timer(lock(timer).clone()),
-        TimerRequest::Subscribe => {
-            broadcast.subscribe(id, lines.clone());
-            return TimerResponse::Subscription(true);
+) -> Result<TimerResponse, Jsonrpc20Error> {
+    let mut timer = lock(&host.timer);
+
+    // NOTE: the tick thread may not have woken since the clock jumped,
+    // so the request deals with the jump first, and applies to the
+    // timer the policy left.
+    let (now, mut jumped) = host.watch.read(&mut timer);
+    host.keep(&mut timer, &mut jumped, now, TimerEnd::Completed);
+    host.broadcast.emit(jumped);
+
+    let end = match request {
+        TimerRequest::Skip => TimerEnd::Skipped,
+        TimerRequest::Stop => TimerEnd::Stopped,
+        TimerRequest::Goto { .. } => TimerEnd::Moved,
+        _ => TimerEnd::Completed,
+    };
+
+    let applied = match request {
+        TimerRequest::Get => {
+            // NOTE: the tick thread sleeps until the timer has something
+            // to report, so the cycle it last computed can be minutes
+            // old, and the copy answered is brought up to date first.
+            let mut timer = timer.clone();
+            let _ = timer.update(now);
+
+            // NOTE: the progress is only counted when a cycle ends, so
+            // the one of a day gone by is brought to this one as well.
+            if let Some(goal) = &host.goal {
+                goal.refresh(&mut timer, now);
+            }
+
+            return Ok(TimerResponse::Timer(Box::new(timer)));
+        }
+        TimerRequest::Subscribe { filter } => {
+            // NOTE: the tick thread counts the subscribers under the
+            // timer lock, so holding it keeps the wake this sends from
+            // landing between that count and the wait after it.
+            host.broadcast.subscribe(id, lines.clone(), filter);
+            return Ok(TimerResponse::Subscription(true));
         }
         TimerRequest::Unsubscribe => {
-            broadcast.unsubscribe(id);
-            return TimerResponse::Subscription(false);
-        }
-        TimerRequest::Start => lock(timer).start(now()).into_iter().collect(),
-        TimerRequest::Pause => lock(timer).pause(now()).into_iter().collect(),
-        TimerRequest::Resume => lock(timer).resume(now()).into_iter().collect(),
-        TimerRequest::Stop => lock(timer).stop().into_iter().collect(),
-        TimerRequest::Set { duration } => lock(timer).set(now(), duration).into_iter().collect(),
+            host.broadcast.unsubscribe(id);
+            return Ok(TimerResponse::Subscription(false));
+        }
+        TimerRequest::Info => return Ok(TimerResponse::Info(host.info.clone())),
+        #[cfg(feature = "schemars")]
+        TimerRequest::Discover => {
+            return Ok(TimerResponse::Document(crate::protocol::openrpc::document()));
+        }
+        TimerRequest::Start { label } => {
+            // NOTE: a stopped timer takes a label silently, so the start
+            // reports the run it begins already labeled. A running one
+            // keeps its own, as it keeps its cycle.
+            if let TimerState::Stopped = timer.state
+                && !label.is_empty()
+            {
+                let _ = timer.label(label.task, label.tags);
+            }
+
+            timer.start(now)
+        }
+        TimerRequest::Label { label } => timer.label(label.task, label.tags),
+        TimerRequest::Interrupt { interruption } => {
+            timer.interrupt(interruption.kind, interruption.note)
+        }
+        TimerRequest::Pause => timer.pause(now),
+        TimerRequest::Resume => timer.resume(now),
+        TimerRequest::Stop => timer.stop(),
+        TimerRequest::Set { duration } => {
+            let duration = duration.saturating_mul(1000);
+            timer.set(now, duration)
+        }
+        TimerRequest::Skip => timer.skip(now),
+        TimerRequest::Extend { delta } => {
+            // NOTE: a delta the timer cannot count is a bad parameter
+            // rather than a state the timer is in, and is refused before
+            // anything changes.
+            match delta
+                .checked_mul(1000)
+                .map(|delta| timer.extend(now, delta))
+            {
+                None | Some(Err(TimerError::OutOfRange)) => {
+                    let err = Jsonrpc20Error::invalid_params("The delta is out of range");
+                    return Err(err.with_data(json!(["delta"])));
+                }
+                Some(applied) => applied,
+            }
+        }
+        TimerRequest::Goto { cycle, loop_index } => {
+            let Some(index) = timer.schedule.find_cycle(&cycle) else {
+                let err = Jsonrpc20Error::invalid_params("The schedule holds no such cycle");
+                return Err(err.with_data(json!(["cycle"])));
+            };
+
+            // NOTE: the cycle is in the schedule, so the timer refusing
+            // to go there refuses the loop: one beyond the configured
+            // loops, or further than it can count.
+            match timer.goto(now, index, loop_index) {
+                Err(TimerError::OutOfSchedule) => {
+                    let err = Jsonrpc20Error::invalid_params("The schedule runs no such loop");
+                    return Err(err.with_data(json!(["loop"])));
+                }
+                applied => applied,
+            }
+        }
     };
 
-    broadcast.emit(events.clone());
-    TimerResponse::Events(events)
-}
+    // NOTE: a refused request changed nothing, so there is nothing to
+    // keep nor to broadcast, only the state that refused it to report.
+    let mut events = applied.map_err(|err| err.into_jsonrpc(&timer.state))?;
 
-/// Returns the current time as whole seconds since the Unix epoch.
-///
-/// A clock set before the epoch is not a recoverable condition for a
-/// timer, so it saturates at zero rather than threading an error
-/// through every call site.
-fn now() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|now| now.as_secs())
-        .unwrap_or_default()
+    host.keep(&mut timer, &mut events, now, end);
+    host.broadcast.emit(events.clone());
+    Ok(TimerResponse::Events(events))
 }
 
 /// Locks `mutex`, recovering the guard when a holder panicked.
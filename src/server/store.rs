@@ -0,0 +1,161 @@
+//! Timer state kept across server restarts.
+//!
+//! A server holds its timer in memory, so a restart would lose it. A
+//! [`TimerStore`] is the file it saves the timer to whenever the timer
+//! changes, and takes it back from when it starts again, reconciling
+//! the time that passed meanwhile as [`TimerRestore`] says.
+//!
+//! The file holds a versioned [`TimerSnapshot`], so a later Comodoro
+//! can read what an earlier one saved, migrating it if its shape moved.
+
+use alloc::format;
+
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result, bail};
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::timer::{Timer, TimerSchedule};
+
+/// The version of the snapshots this Comodoro writes.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// What a store holds: the timer, and when it was saved.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TimerSnapshot {
+    /// The shape of the snapshot, bumped whenever a field of it or of
+    /// the timer changes in a way older readers would misread.
+    pub version: u32,
+    /// Unix epoch milliseconds at which the timer was saved.
+    pub saved_at: u64,
+    /// The timer as of then.
+    pub timer: Timer,
+}
+
+/// How a server takes back a timer that was running when it stopped.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimerRestore {
+    /// The timer runs on as if the server never stopped, the time it
+    /// was down counted, and the first tick reports the cycle it lands
+    /// in.
+    #[default]
+    Resume,
+    /// The timer is paused as of when it was saved, so the time the
+    /// server was down counts for nothing.
+    Pause,
+}
+
+/// The file a server keeps its timer in across restarts.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimerStore {
+    /// Where the snapshot is written.
+    pub path: PathBuf,
+    /// How a timer that was running is taken back.
+    pub restore: TimerRestore,
+    /// How old, in milliseconds, a snapshot can be and still be taken
+    /// back, with no limit when absent.
+    pub stale_after: Option<u64>,
+}
+
+impl TimerStore {
+    /// Reads the timer saved in the store, reconciled with `now`, in
+    /// Unix epoch milliseconds.
+    ///
+    /// Returns `None` when there is nothing to take back: no snapshot,
+    /// one older than [`Self::stale_after`], or one running another
+    /// schedule than `schedule`, since its place in that schedule would
+    /// mean nothing in this one.
+    pub fn load(&self, schedule: &TimerSchedule, now: u64) -> Result<Option<Timer>> {
+        let path = self.path.display();
+
+        let json = match fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("Read state file {path} error")),
+        };
+
+        let snapshot = parse(&json).with_context(|| format!("Parse state file {path} error"))?;
+        let TimerSnapshot {
+            saved_at,
+            mut timer,
+            ..
+        } = snapshot;
+
+        if let Some(stale_after) = self.stale_after
+            && now.saturating_sub(saved_at) > stale_after
+        {
+            info!("discard state file {path}, saved too long ago");
+            return Ok(None);
+        }
+
+        if timer.schedule != *schedule {
+            info!("discard state file {path}, saved for another schedule");
+            return Ok(None);
+        }
+
+        if let TimerRestore::Pause = self.restore {
+            let _ = timer.pause(saved_at);
+        }
+
+        Ok(Some(timer))
+    }
+
+    /// Writes `timer` to the store as of `now`, in Unix epoch
+    /// milliseconds.
+    ///
+    /// The snapshot is written beside the store then renamed over it,
+    /// so a crash mid-write leaves the previous one whole.
+    pub fn save(&self, timer: &Timer, now: u64) -> Result<()> {
+        let path = self.path.display();
+
+        let snapshot = TimerSnapshot {
+            version: SNAPSHOT_VERSION,
+            saved_at: now,
+            timer: timer.clone(),
+        };
+
+        let json = serde_json::to_string(&snapshot).context("Serialize timer error")?;
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Create state directory {} error", dir.display()))?;
+        }
+
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(".tmp");
+        let tmp = self.path.with_file_name(name);
+
+        let mut file = fs::File::create(&tmp)
+            .with_context(|| format!("Create state file {} error", tmp.display()))?;
+        file.write_all(json.as_bytes())
+            .and_then(|()| file.sync_all())
+            .with_context(|| format!("Write state file {} error", tmp.display()))?;
+
+        fs::rename(&tmp, &self.path).with_context(|| format!("Replace state file {path} error"))
+    }
+}
+
+/// Parses a snapshot of any version this Comodoro knows, migrating it
+/// to the current one.
+fn parse(json: &str) -> Result<TimerSnapshot> {
+    #[derive(Deserialize)]
+    struct Versioned {
+        version: u32,
+    }
+
+    let Versioned { version } = serde_json::from_str(json)?;
+
+    // NOTE: a migration lands here as an arm rewriting the JSON of its
+    // version into the shape of the next, falling through to the
+    // current one.
+    match version {
+        SNAPSHOT_VERSION => Ok(serde_json::from_str(json)?),
+        version => bail!("Unsupported snapshot version {version}, expected {SNAPSHOT_VERSION}"),
+    }
+}
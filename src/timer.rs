@@ -2,12 +2,20 @@
 //!
 //! The [`Timer`] struct is I/O-free: it never reads the clock itself.
 //! Methods that need the current time accept `now: u64` (Unix epoch
-//! seconds) as a parameter, so the caller decides where time comes
-//! from. This is the whole of Comodoro's logic, and it knows nothing
+//! milliseconds) as a parameter, so the caller decides where time
+//! comes from. This is the whole of Comodoro's logic, and it knows nothing
 //! about sockets, JSON-RPC or configuration.
 
+use core::{
+    convert::Infallible,
+    fmt,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
 use alloc::{
     string::{String, ToString},
+    vec,
     vec::Vec,
 };
 
@@ -37,30 +45,371 @@ impl From<usize> for TimerLoop {
 }
 
 /// A single step in the timer lifecycle, identified by a name and a
-/// duration in seconds.
+/// duration in milliseconds.
+///
+/// A cycle read without a duration is a count-up cycle, which is how a
+/// stopwatch is configured.
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(from = "TimerCycleDef", into = "TimerCycleDef")]
 pub struct TimerCycle {
     /// The name of this cycle.
     pub name: String,
-    /// Remaining seconds in this cycle.
+    /// Remaining milliseconds in this cycle.
     ///
     /// From the *configuration* perspective this is the total cycle
     /// duration; from the *running timer* perspective it is the time
-    /// remaining before the cycle ends.
+    /// remaining before the cycle ends, or the time it ran so far in a
+    /// count-up cycle.
     pub duration: usize,
+    /// Whether the next cycle begins as soon as this one ends.
+    ///
+    /// When false, the timer ending this cycle waits in
+    /// [`TimerState::Waiting`] until a client resumes it. Left out of
+    /// the serialized cycle when true, which is the default, so a cycle
+    /// reads the same as before the field existed.
+    pub auto_advance: bool,
+    /// Whether the timer keeps counting once this cycle reaches zero.
+    ///
+    /// When true, the timer ending this cycle counts the time past its
+    /// end in [`TimerState::Overtime`] until a client acknowledges it,
+    /// which takes precedence over [`Self::auto_advance`]. Left out of
+    /// the serialized cycle when false, which is the default.
+    pub overtime: bool,
+    /// Whether this cycle counts up until a client ends it, rather than
+    /// down from a configured duration.
+    ///
+    /// A count-up cycle lasts no schedule time: its configured duration
+    /// is ignored, and the time it ran shifts the cycles after it once
+    /// it ends, as an extension would. From the running timer
+    /// perspective, [`Self::duration`] is the time it ran so far.
+    pub count_up: bool,
+    /// The share of the time the preceding count-up cycle ran that this
+    /// cycle lasts, when it follows one.
+    ///
+    /// [`Self::duration`] is then the shortest this cycle lasts, and
+    /// [`Self::max_duration`] the longest, the former winning when they
+    /// disagree. This is what a Flowtime rest
+    /// is: a fifth of the work it follows, for example.
+    pub ratio: Option<TimerRatio>,
+    /// The longest this cycle lasts, in milliseconds, when
+    /// [`Self::ratio`] derives its duration. Unbounded when absent.
+    pub max_duration: Option<usize>,
+    /// The iteration of each group this cycle was flattened from, the
+    /// outermost first.
+    ///
+    /// Empty for a cycle configured outside any group, and left out of
+    /// the serialized cycle then.
+    pub iterations: Vec<TimerIteration>,
 }
 
 impl TimerCycle {
-    /// Creates a new cycle with the given name and duration.
+    /// Creates a new cycle with the given name and duration in
+    /// milliseconds, advancing to the next one on its own.
     pub fn new(name: impl ToString, duration: usize) -> Self {
         Self {
             name: name.to_string(),
             duration,
+            auto_advance: true,
+            overtime: false,
+            count_up: false,
+            ratio: None,
+            max_duration: None,
+            iterations: Vec::new(),
+        }
+    }
+
+    /// Creates a new cycle with the given name, counting up until a
+    /// client ends it.
+    pub fn count_up(name: impl ToString) -> Self {
+        let mut cycle = Self::new(name, 0);
+        cycle.count_up = true;
+        cycle
+    }
+
+    /// Makes the timer wait for a client once this cycle ends, rather
+    /// than beginning the next one.
+    pub fn manual(mut self) -> Self {
+        self.auto_advance = false;
+        self
+    }
+
+    /// Makes the timer count overtime once this cycle ends, rather than
+    /// beginning the next one.
+    pub fn overtime(mut self) -> Self {
+        self.overtime = true;
+        self
+    }
+
+    /// Makes this cycle last `ratio` of the time the count-up cycle
+    /// preceding it ran, no less than its duration and no more than
+    /// `max_duration` milliseconds when given, unless that is below its
+    /// duration.
+    pub fn ratio(mut self, ratio: impl Into<TimerRatio>, max_duration: Option<usize>) -> Self {
+        self.ratio = Some(ratio.into());
+        self.max_duration = max_duration;
+        self
+    }
+
+    /// The duration this cycle lasts after a count-up cycle that ran
+    /// `counted` milliseconds.
+    fn derived_duration(&self, counted: usize) -> usize {
+        match self.ratio {
+            None => self.duration,
+            // NOTE: the duration is the floor even under a lower
+            // maximum, so the derived duration never falls below the
+            // one the schedule counts for this cycle.
+            Some(ratio) => ratio
+                .of(counted)
+                .min(self.max_duration.unwrap_or(usize::MAX))
+                .max(self.duration),
+        }
+    }
+}
+
+impl Default for TimerCycle {
+    fn default() -> Self {
+        Self::new(String::new(), 0)
+    }
+}
+
+/// The shape a [`TimerCycle`] is read from and written as, where the
+/// duration is optional.
+///
+/// Durations travel in whole seconds, as they did before the timer
+/// counted milliseconds, and in milliseconds beside them. Reading
+/// prefers the milliseconds, and falls back to the seconds a
+/// configuration or an older peer gives.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Deserialize, Serialize)]
+struct TimerCycleDef {
+    /// The name of this cycle.
+    name: String,
+    /// The duration of this cycle in whole seconds, counting up when
+    /// absent along with `duration-ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duration: Option<usize>,
+    /// The duration of this cycle in milliseconds.
+    #[serde(
+        rename = "duration-ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    duration_ms: Option<usize>,
+    /// Whether the next cycle begins as soon as this one ends.
+    #[serde(
+        rename = "auto-advance",
+        default = "auto_advance",
+        skip_serializing_if = "is_auto_advance"
+    )]
+    auto_advance: bool,
+    /// Whether the timer keeps counting once this cycle reaches zero.
+    #[serde(default, skip_serializing_if = "is_false")]
+    overtime: bool,
+    /// Whether this cycle counts up until a client ends it.
+    #[serde(rename = "count-up", default, skip_serializing_if = "is_false")]
+    count_up: bool,
+    /// The share of the preceding count-up cycle this cycle lasts.
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<f64>"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ratio: Option<TimerRatio>,
+    /// The longest this cycle lasts when its ratio derives it, in
+    /// whole seconds.
+    #[serde(
+        rename = "max-duration",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    max_duration: Option<usize>,
+    /// The longest this cycle lasts when its ratio derives it, in
+    /// milliseconds.
+    #[serde(
+        rename = "max-duration-ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    max_duration_ms: Option<usize>,
+    /// The iteration of each group this cycle was flattened from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    iterations: Vec<TimerIteration>,
+}
+
+impl From<TimerCycleDef> for TimerCycle {
+    fn from(cycle: TimerCycleDef) -> Self {
+        let duration = cycle.duration_ms.or(cycle.duration.map(secs_to_millis));
+
+        Self {
+            name: cycle.name,
+            duration: duration.unwrap_or_default(),
+            auto_advance: cycle.auto_advance,
+            overtime: cycle.overtime,
+            count_up: cycle.count_up || duration.is_none(),
+            ratio: cycle.ratio,
+            max_duration: cycle
+                .max_duration_ms
+                .or(cycle.max_duration.map(secs_to_millis)),
+            iterations: cycle.iterations,
+        }
+    }
+}
+
+impl From<TimerCycle> for TimerCycleDef {
+    fn from(cycle: TimerCycle) -> Self {
+        Self {
+            name: cycle.name,
+            duration: Some(cycle.duration / 1000),
+            duration_ms: Some(cycle.duration),
+            auto_advance: cycle.auto_advance,
+            overtime: cycle.overtime,
+            count_up: cycle.count_up,
+            ratio: cycle.ratio,
+            max_duration: cycle.max_duration.map(|max| max / 1000),
+            max_duration_ms: cycle.max_duration,
+            iterations: cycle.iterations,
+        }
+    }
+}
+
+/// A cycle as the timer runs it: the configured cycle, with what the
+/// timer carries along while running it.
+///
+/// Only a running timer has any of it, so a schedule, and a
+/// configuration, holds plain [`TimerCycle`]s. Serialized as the cycle
+/// it wraps, with its own members beside the configured ones.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TimerCycleRun {
+    /// The cycle, carrying its remaining duration.
+    #[serde(flatten)]
+    pub cycle: TimerCycle,
+    /// The free-text task the timer runs for, if a client labeled it.
+    ///
+    /// The timer carries it from cycle to cycle until it stops, so
+    /// every event about a cycle tells what the time was spent on. Left
+    /// out of the serialized cycle when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// The tags of the task the timer runs for, carried like
+    /// [`Self::task`]. Left out of the serialized cycle when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The interruptions recorded during this cycle, oldest first.
+    ///
+    /// Carried like [`Self::task`], but for this cycle alone: the next
+    /// one begins without any. Left out of the serialized cycle when
+    /// empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interruptions: Vec<TimerInterruption>,
+}
+
+impl From<TimerCycle> for TimerCycleRun {
+    fn from(cycle: TimerCycle) -> Self {
+        Self {
+            cycle,
+            task: None,
+            tags: Vec::new(),
+            interruptions: Vec::new(),
         }
     }
 }
 
+impl TimerCycleRun {
+    /// How many interruptions of `kind` were recorded during this
+    /// cycle.
+    pub fn interruptions_of(&self, kind: TimerInterruptionKind) -> usize {
+        self.interruptions
+            .iter()
+            .filter(|interruption| interruption.kind == kind)
+            .count()
+    }
+}
+
+impl Deref for TimerCycleRun {
+    type Target = TimerCycle;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cycle
+    }
+}
+
+impl DerefMut for TimerCycleRun {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cycle
+    }
+}
+
+fn secs_to_millis(secs: usize) -> usize {
+    secs.saturating_mul(1000)
+}
+
+fn auto_advance() -> bool {
+    true
+}
+
+fn is_auto_advance(auto_advance: &bool) -> bool {
+    *auto_advance
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// A ratio between two durations, such as `0.2` for a fifth.
+///
+/// Kept in thousandths rather than as a float, so a cycle carrying one
+/// stays comparable, and read from and written to a float.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(from = "f64", into = "f64")]
+pub struct TimerRatio(u32);
+
+impl TimerRatio {
+    /// `duration` scaled by this ratio, rounded down.
+    pub fn of(self, duration: usize) -> usize {
+        (duration as u64 * self.0 as u64 / 1000) as usize
+    }
+}
+
+impl From<f64> for TimerRatio {
+    fn from(ratio: f64) -> Self {
+        // NOTE: no_std offers no rounding, hence the half added before
+        // the cast, which saturates negative ratios to zero.
+        Self((ratio * 1000.0 + 0.5) as u32)
+    }
+}
+
+impl From<TimerRatio> for f64 {
+    fn from(ratio: TimerRatio) -> Self {
+        ratio.0 as f64 / 1000.0
+    }
+}
+
+/// A cycle of a schedule, named either by its index or by its name.
+///
+/// Serialized untagged, so a number names the cycle at that zero-based
+/// index and a string names the first cycle carrying that name.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TimerCycleRef {
+    /// The cycle at this zero-based index.
+    Index(usize),
+    /// The first cycle carrying this name.
+    Name(String),
+}
+
+/// Reads a number as an index and anything else as a name, which is
+/// what a command-line argument naming a cycle means.
+impl FromStr for TimerCycleRef {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse() {
+            Ok(index) => Self::Index(index),
+            Err(_) => Self::Name(s.to_string()),
+        })
+    }
+}
+
 /// The current state of a timer.
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -69,6 +418,15 @@ pub enum TimerState {
     Running,
     /// The timer has been paused.
     Paused,
+    /// The timer ended a cycle that does not advance on its own, and
+    /// waits for a client to begin the next one.
+    Waiting,
+    /// The timer ended a cycle counting overtime, and counts the time
+    /// past its end until a client acknowledges it.
+    ///
+    /// The current cycle then carries that overtime as its duration
+    /// rather than a remaining time.
+    Overtime,
     /// The timer is not running.
     #[default]
     Stopped,
@@ -76,39 +434,324 @@ pub enum TimerState {
 
 /// An event emitted by a timer during its lifecycle.
 ///
-/// Serialized adjacently, as `{"event": "began", "cycle": {…}}`, so an
-/// event stays self-describing wherever it travels without a method
-/// name next to it. See [`crate::protocol`] for the wire mapping.
+/// Serialized with its kind beside what it carries, as
+/// `{"event": "began", "cycle": {…}}` or `{"event": "loopBegan",
+/// "loop": 1}`, so an event stays self-describing wherever it travels
+/// without a method name next to it. See [`crate::protocol`] for the
+/// wire mapping.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(tag = "event", content = "cycle", rename_all = "camelCase")]
+#[serde(from = "TimerEventDef", into = "TimerEventDef")]
 pub enum TimerEvent {
     /// The timer started.
     Started,
     /// The timer began the given cycle.
-    Began(TimerCycle),
+    Began(TimerCycleRun),
     /// The timer is running the given cycle (periodic tick).
     ///
     /// Carries the remaining duration as of that tick, so two
     /// consecutive ones never carry the same duration.
-    Running(TimerCycle),
+    Running(TimerCycleRun),
     /// The remaining duration was manually set.
-    Set(TimerCycle),
+    Set(TimerCycleRun),
+    /// The current cycle was extended or shortened, carrying its new
+    /// remaining duration.
+    Extended(TimerCycleRun),
     /// The timer was paused at the given cycle.
-    Paused(TimerCycle),
+    Paused(TimerCycleRun),
     /// The timer was resumed at the given cycle.
-    Resumed(TimerCycle),
+    Resumed(TimerCycleRun),
     /// The timer ended the given cycle.
-    Ended(TimerCycle),
+    Ended(TimerCycleRun),
+    /// The timer waits for a client before beginning the given cycle,
+    /// since the one before it does not advance on its own.
+    Awaiting(TimerCycleRun),
+    /// The timer is counting overtime past the end of the given cycle
+    /// (periodic tick).
+    ///
+    /// Carries the overtime as of that tick, so two consecutive ones
+    /// never carry the same duration.
+    Overtime(TimerCycleRun),
+    /// The task the timer runs for changed, carrying the current cycle
+    /// with the new one.
+    Labeled(TimerCycleRun),
+    /// An interruption was recorded, carrying the current cycle, whose
+    /// last interruption is the new one.
+    Interrupted(TimerCycleRun),
+    /// The cycle that just ended met the daily goal.
+    ///
+    /// Carries nothing, like the other timer-wide events: the progress
+    /// is the timer's [`Timer::goal`].
+    GoalReached,
+    /// The timer began the loop of the given zero-based index.
+    LoopBegan(usize),
+    /// The loop of the given zero-based index ran out, its last cycle
+    /// having ended.
+    LoopEnded(usize),
+    /// The timer ran every loop it was configured for, right before it
+    /// stops.
+    Completed,
     /// The timer stopped.
     Stopped,
 }
 
+/// The shape a [`TimerEvent`] is read from and written as.
+///
+/// Tagged internally rather than adjacently, since the loop events
+/// carry an index rather than a cycle, and an index under `cycle`
+/// would read as one.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum TimerEventDef {
+    Started,
+    Began {
+        cycle: TimerCycleRun,
+    },
+    Running {
+        cycle: TimerCycleRun,
+    },
+    Set {
+        cycle: TimerCycleRun,
+    },
+    Extended {
+        cycle: TimerCycleRun,
+    },
+    Paused {
+        cycle: TimerCycleRun,
+    },
+    Resumed {
+        cycle: TimerCycleRun,
+    },
+    Ended {
+        cycle: TimerCycleRun,
+    },
+    Awaiting {
+        cycle: TimerCycleRun,
+    },
+    Overtime {
+        cycle: TimerCycleRun,
+    },
+    Labeled {
+        cycle: TimerCycleRun,
+    },
+    Interrupted {
+        cycle: TimerCycleRun,
+    },
+    GoalReached,
+    LoopBegan {
+        #[serde(rename = "loop")]
+        index: usize,
+    },
+    LoopEnded {
+        #[serde(rename = "loop")]
+        index: usize,
+    },
+    Completed,
+    Stopped,
+}
+
+impl From<TimerEventDef> for TimerEvent {
+    fn from(event: TimerEventDef) -> Self {
+        match event {
+            TimerEventDef::Started => Self::Started,
+            TimerEventDef::Began { cycle } => Self::Began(cycle),
+            TimerEventDef::Running { cycle } => Self::Running(cycle),
+            TimerEventDef::Set { cycle } => Self::Set(cycle),
+            TimerEventDef::Extended { cycle } => Self::Extended(cycle),
+            TimerEventDef::Paused { cycle } => Self::Paused(cycle),
+            TimerEventDef::Resumed { cycle } => Self::Resumed(cycle),
+            TimerEventDef::Ended { cycle } => Self::Ended(cycle),
+            TimerEventDef::Awaiting { cycle } => Self::Awaiting(cycle),
+            TimerEventDef::Overtime { cycle } => Self::Overtime(cycle),
+            TimerEventDef::Labeled { cycle } => Self::Labeled(cycle),
+            TimerEventDef::Interrupted { cycle } => Self::Interrupted(cycle),
+            TimerEventDef::GoalReached => Self::GoalReached,
+            TimerEventDef::LoopBegan { index } => Self::LoopBegan(index),
+            TimerEventDef::LoopEnded { index } => Self::LoopEnded(index),
+            TimerEventDef::Completed => Self::Completed,
+            TimerEventDef::Stopped => Self::Stopped,
+        }
+    }
+}
+
+impl From<TimerEvent> for TimerEventDef {
+    fn from(event: TimerEvent) -> Self {
+        match event {
+            TimerEvent::Started => Self::Started,
+            TimerEvent::Began(cycle) => Self::Began { cycle },
+            TimerEvent::Running(cycle) => Self::Running { cycle },
+            TimerEvent::Set(cycle) => Self::Set { cycle },
+            TimerEvent::Extended(cycle) => Self::Extended { cycle },
+            TimerEvent::Paused(cycle) => Self::Paused { cycle },
+            TimerEvent::Resumed(cycle) => Self::Resumed { cycle },
+            TimerEvent::Ended(cycle) => Self::Ended { cycle },
+            TimerEvent::Awaiting(cycle) => Self::Awaiting { cycle },
+            TimerEvent::Overtime(cycle) => Self::Overtime { cycle },
+            TimerEvent::Labeled(cycle) => Self::Labeled { cycle },
+            TimerEvent::Interrupted(cycle) => Self::Interrupted { cycle },
+            TimerEvent::GoalReached => Self::GoalReached,
+            TimerEvent::LoopBegan(index) => Self::LoopBegan { index },
+            TimerEvent::LoopEnded(index) => Self::LoopEnded { index },
+            TimerEvent::Completed => Self::Completed,
+            TimerEvent::Stopped => Self::Stopped,
+        }
+    }
+}
+
+/// Why a timer refused a request.
+///
+/// Returned by the methods a client calls, instead of the events a
+/// request that cannot apply would otherwise not emit, so the caller
+/// tells a refusal apart from a request that changed nothing visible.
+/// See [`crate::protocol`] for the code each one travels as.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimerError {
+    /// The timer is stopped, and the request needs a run under way.
+    Stopped,
+    /// The timer is paused, waiting or counting overtime, and the
+    /// request needs it running.
+    NotRunning,
+    /// The timer is running already.
+    AlreadyRunning,
+    /// The current cycle counts up, and has no duration to act on.
+    CountingUp,
+    /// The cycles of the schedule add up to no time.
+    NoTime,
+    /// The cycle or the loop requested is not in the schedule.
+    OutOfSchedule,
+    /// The duration requested does not fit the time the timer counts.
+    OutOfRange,
+}
+
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stopped => write!(f, "The timer is stopped"),
+            Self::NotRunning => write!(f, "The timer is not running"),
+            Self::AlreadyRunning => write!(f, "The timer is already running"),
+            Self::CountingUp => write!(f, "The current cycle counts up"),
+            Self::NoTime => write!(f, "The cycles add up to no time"),
+            Self::OutOfSchedule => write!(f, "The schedule holds no such cycle or loop"),
+            Self::OutOfRange => write!(f, "The duration is out of range"),
+        }
+    }
+}
+
+impl core::error::Error for TimerError {}
+
+/// Something that broke the focus of a cycle, as Pomodoro counts them.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TimerInterruption {
+    /// Where the interruption came from.
+    pub kind: TimerInterruptionKind,
+    /// What the interruption was about, if told.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Where an interruption came from.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerInterruptionKind {
+    /// The one running the timer broke off on their own, to check mail
+    /// for example.
+    #[default]
+    Internal,
+    /// Someone or something else broke in, such as a call.
+    External,
+}
+
+/// Where a cycle stands in a group it was flattened from.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TimerIteration {
+    /// The zero-based iteration of the group.
+    pub index: usize,
+    /// How many times the group repeats.
+    pub count: usize,
+}
+
+/// An entry of a schedule as configured: a cycle, or a group of
+/// entries repeated a number of times.
+///
+/// Untagged, since the shapes tell each other apart: a group carries no
+/// name, and a cycle no `repeat`.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TimerEntry {
+    /// Entries repeated a number of times.
+    Group(TimerGroup),
+    /// A single cycle.
+    Cycle(TimerCycle),
+}
+
+impl TimerEntry {
+    /// Flattens `entries` into the cycle sequence a schedule runs, each
+    /// cycle of a group carrying the iteration it belongs to.
+    pub fn flatten(entries: &[TimerEntry]) -> Vec<TimerCycle> {
+        let mut cycles = Vec::new();
+
+        for entry in entries {
+            entry.flatten_into(&mut cycles, &[]);
+        }
+
+        cycles
+    }
+
+    fn flatten_into(&self, cycles: &mut Vec<TimerCycle>, iterations: &[TimerIteration]) {
+        match self {
+            Self::Cycle(cycle) => {
+                let mut cycle = cycle.clone();
+                cycle.iterations.splice(0..0, iterations.iter().cloned());
+                cycles.push(cycle);
+            }
+            Self::Group(group) => {
+                for index in 0..group.repeat {
+                    let mut iterations = iterations.to_vec();
+                    iterations.push(TimerIteration {
+                        index,
+                        count: group.repeat,
+                    });
+
+                    for entry in &group.cycles {
+                        entry.flatten_into(cycles, &iterations);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<TimerCycle> for TimerEntry {
+    fn from(cycle: TimerCycle) -> Self {
+        Self::Cycle(cycle)
+    }
+}
+
+/// Entries of a schedule repeated a number of times, such as the work
+/// and rest pairs preceding a long rest.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimerGroup {
+    /// How many times the entries run.
+    pub repeat: usize,
+    /// The entries, cycles or nested groups, in the order they run.
+    pub cycles: Vec<TimerEntry>,
+}
+
 /// What a timer runs: the ordered cycles, and how many loops of them.
 ///
 /// Named after what it describes rather than after where it comes from,
 /// since a CLI has a configuration of its own and this is not it.
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct TimerSchedule {
     /// The ordered list of timer cycles.
     pub cycles: Vec<TimerCycle>,
@@ -118,6 +761,18 @@ pub struct TimerSchedule {
 }
 
 impl TimerSchedule {
+    /// The index of the cycle `cycle` refers to, if the schedule holds
+    /// one.
+    ///
+    /// A name resolves to the first cycle carrying it, since a
+    /// configuration is free to run the same name twice.
+    pub fn find_cycle(&self, cycle: &TimerCycleRef) -> Option<usize> {
+        match cycle {
+            TimerCycleRef::Index(index) => (*index < self.cycles.len()).then_some(*index),
+            TimerCycleRef::Name(name) => self.cycles.iter().position(|c| &c.name == name),
+        }
+    }
+
     fn first_cycle(&self) -> TimerCycle {
         self.cycles
             .first()
@@ -131,50 +786,175 @@ impl TimerSchedule {
     ///
     /// This is the view the timer reads the current cycle from: an
     /// elapsed time names a cycle by falling before its end and after
-    /// the end of the one preceding it.
-    ///
-    /// Empty when the cycles are, and when they all last no time, since
-    /// no elapsed time can name a cycle in either case.
+    /// the end of the one preceding it. A count-up cycle lasts no
+    /// schedule time, so no elapsed time names it.
     fn cumulated_cycles(&self) -> Vec<TimerCycle> {
-        let cycles: Vec<_> = self
-            .cycles
+        self.cycles
             .iter()
             .cloned()
             .scan(0, |end, mut cycle| {
-                *end += cycle.duration;
+                if !cycle.count_up {
+                    *end += cycle.duration;
+                }
                 cycle.duration = *end;
                 Some(cycle)
             })
-            .collect();
-
-        match cycles.last() {
-            Some(last) if last.duration > 0 => cycles,
-            _ => Vec::new(),
-        }
+            .collect()
     }
 }
 
 /// An I/O-free timer state machine.
 ///
 /// All methods that depend on the current time accept `now: u64`
-/// (seconds since the Unix epoch) rather than reading the clock
+/// (milliseconds since the Unix epoch) rather than reading the clock
 /// internally, which is what keeps the timer testable without a clock
 /// and usable under no_std.
+///
+/// The current cycle is derived rather than stored: the elapsed time,
+/// shifted back by [`Self::extended`], is a schedule time, and a
+/// schedule time names a loop and a cycle of it. Every time the timer
+/// holds is in milliseconds, so no transition rounds one away.
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(from = "TimerDef", into = "TimerDef")]
 pub struct Timer {
     /// What the timer runs: its cycles, and how many loops of them.
     pub schedule: TimerSchedule,
     /// The current timer state.
     pub state: TimerState,
     /// The current cycle (with remaining duration).
-    pub cycle: TimerCycle,
-    /// Unix epoch seconds at which the timer was last started or
+    pub cycle: TimerCycleRun,
+    /// Unix epoch milliseconds at which the timer was last started or
     /// resumed. `None` when the timer is stopped or paused.
     pub started_at: Option<u64>,
-    /// Accumulated elapsed seconds from previous runs (before the
+    /// Accumulated elapsed milliseconds from previous runs (before the
     /// last pause or stop).
     pub elapsed: usize,
+    /// Milliseconds the cycles were extended by so far, negative when
+    /// they were shortened.
+    ///
+    /// The schedule runs this far behind the elapsed time, so an
+    /// extension shifts every cycle after the extended one rather than
+    /// rewriting the configured durations.
+    pub extended: isize,
+    /// The schedule time, in milliseconds, the current cycle is looked
+    /// up from no earlier than.
+    ///
+    /// An extension longer than what the cycle already ran puts the
+    /// schedule time before the start of that cycle, where it would
+    /// otherwise name the cycle preceding it. The floor also lies in the
+    /// current cycle, which is how a tick tells the cycle it ends.
+    pub floor: usize,
+    /// The index of the count-up cycle the timer is in, if any.
+    ///
+    /// A count-up cycle lasts no schedule time, so no schedule time
+    /// names it, and the timer holds its schedule time at
+    /// [`Self::floor`], where the cycle began, until a client ends it.
+    pub counting: Option<usize>,
+    /// The index of the loop the timer is in.
+    ///
+    /// Tracked rather than derived from the schedule time, since a
+    /// stopwatch runs no schedule time to derive it from.
+    pub loop_index: usize,
+    /// How far the day went towards the daily goal, if one is set.
+    ///
+    /// The timer carries it without ever changing it: whoever records
+    /// the cycles that end, usually the server, keeps it up to date.
+    pub goal: Option<TimerGoalProgress>,
+}
+
+/// The shape a [`Timer`] is read from and written as.
+///
+/// Every time travels in whole seconds, as it did before the timer
+/// counted milliseconds, and in milliseconds under the same name
+/// suffixed with `_ms`. Reading prefers the milliseconds, and falls
+/// back to the seconds an older peer gives.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Deserialize, Serialize)]
+struct TimerDef {
+    /// What the timer runs.
+    schedule: TimerSchedule,
+    /// The current timer state.
+    state: TimerState,
+    /// The current cycle.
+    cycle: TimerCycleRun,
+    /// Unix epoch seconds at which the timer was last started or
+    /// resumed.
+    started_at: Option<u64>,
+    /// Unix epoch milliseconds at which the timer was last started or
+    /// resumed.
+    #[serde(default)]
+    started_at_ms: Option<u64>,
+    /// Accumulated elapsed seconds from previous runs.
+    elapsed: usize,
+    /// Accumulated elapsed milliseconds from previous runs.
+    #[serde(default)]
+    elapsed_ms: Option<usize>,
+    /// Seconds the cycles were extended by so far.
+    #[serde(default)]
+    extended: isize,
+    /// Milliseconds the cycles were extended by so far.
+    #[serde(default)]
+    extended_ms: Option<isize>,
+    /// The schedule time, in seconds, the current cycle is looked up
+    /// from no earlier than.
+    #[serde(default)]
+    floor: usize,
+    /// The schedule time, in milliseconds, the current cycle is looked
+    /// up from no earlier than.
+    #[serde(default)]
+    floor_ms: Option<usize>,
+    /// The index of the count-up cycle the timer is in, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    counting: Option<usize>,
+    /// The index of the loop the timer is in.
+    #[serde(rename = "loop", default)]
+    loop_index: usize,
+    /// How far the day went towards the daily goal, if one is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    goal: Option<TimerGoalProgress>,
+}
+
+impl From<TimerDef> for Timer {
+    fn from(timer: TimerDef) -> Self {
+        Self {
+            schedule: timer.schedule,
+            state: timer.state,
+            cycle: timer.cycle,
+            started_at: timer
+                .started_at_ms
+                .or(timer.started_at.map(|secs| secs.saturating_mul(1000))),
+            elapsed: timer.elapsed_ms.unwrap_or(secs_to_millis(timer.elapsed)),
+            extended: timer
+                .extended_ms
+                .unwrap_or(timer.extended.saturating_mul(1000)),
+            floor: timer.floor_ms.unwrap_or(secs_to_millis(timer.floor)),
+            counting: timer.counting,
+            loop_index: timer.loop_index,
+            goal: timer.goal,
+        }
+    }
+}
+
+impl From<Timer> for TimerDef {
+    fn from(timer: Timer) -> Self {
+        Self {
+            schedule: timer.schedule,
+            state: timer.state,
+            cycle: timer.cycle,
+            started_at: timer.started_at.map(|ms| ms / 1000),
+            started_at_ms: timer.started_at,
+            elapsed: timer.elapsed / 1000,
+            elapsed_ms: Some(timer.elapsed),
+            extended: timer.extended / 1000,
+            extended_ms: Some(timer.extended),
+            floor: timer.floor / 1000,
+            floor_ms: Some(timer.floor),
+            counting: timer.counting,
+            loop_index: timer.loop_index,
+            goal: timer.goal,
+        }
+    }
 }
 
 impl Timer {
@@ -184,7 +964,7 @@ impl Timer {
     ///
     /// Panics if `schedule` has no cycles.
     pub fn new(schedule: TimerSchedule) -> Self {
-        let cycle = schedule.first_cycle();
+        let cycle = schedule.first_cycle().into();
 
         Self {
             schedule,
@@ -193,8 +973,9 @@ impl Timer {
         }
     }
 
-    /// Returns the total elapsed seconds since the timer last started
-    /// or resumed, plus any previously accumulated elapsed time.
+    /// Returns the total elapsed milliseconds since the timer last
+    /// started or resumed, plus any previously accumulated elapsed
+    /// time.
     pub fn elapsed(&self, now: u64) -> usize {
         let running = self
             .started_at
@@ -203,6 +984,17 @@ impl Timer {
         running + self.elapsed
     }
 
+    /// Returns the milliseconds remaining in the current cycle,
+    /// negative when the timer is counting overtime past its end, or
+    /// counting up a cycle that has none.
+    pub fn remaining(&self) -> isize {
+        match self.state {
+            TimerState::Overtime => -(self.cycle.duration as isize),
+            _ if self.counting.is_some() => -(self.cycle.duration as isize),
+            _ => self.cycle.duration as isize,
+        }
+    }
+
     /// Advances the timer by one tick and returns any events that
     /// fired.
     ///
@@ -210,191 +1002,1026 @@ impl Timer {
     /// tick staying inside its cycle emits [`TimerEvent::Running`], a
     /// tick crossing into another emits [`TimerEvent::Ended`] then
     /// [`TimerEvent::Began`] instead, and a tick changing nothing emits
-    /// nothing, which is what a tick landing less than a second after a
-    /// [`Self::set`] does.
+    /// nothing, which is what a tick landing in the same millisecond as
+    /// a [`Self::set`] does.
+    ///
+    /// A tick crossing into another loop emits [`TimerEvent::LoopEnded`]
+    /// then [`TimerEvent::LoopBegan`] between the two. The tick
+    /// completing the last configured loop emits [`TimerEvent::Ended`],
+    /// [`TimerEvent::LoopEnded`], [`TimerEvent::Completed`] then
+    /// [`TimerEvent::Stopped`], and resets the timer as [`Self::stop`]
+    /// would.
+    ///
+    /// The tick ending a cycle that does not advance on its own emits
+    /// [`TimerEvent::Ended`] then [`TimerEvent::Awaiting`] instead of
+    /// [`TimerEvent::Began`], and holds the timer at the boundary in
+    /// [`TimerState::Waiting`] until [`Self::resume`].
     ///
-    /// The tick completing the last configured loop emits
-    /// [`TimerEvent::Ended`] then [`TimerEvent::Stopped`], and resets
-    /// the timer, exactly as [`Self::stop`] would.
+    /// The tick ending a cycle counting overtime emits
+    /// [`TimerEvent::Ended`] then [`TimerEvent::Overtime`], and keeps
+    /// counting in [`TimerState::Overtime`] until [`Self::resume`]. Every
+    /// later tick emits [`TimerEvent::Overtime`] as long as the overtime
+    /// grows.
     ///
-    /// Has no effect when the timer is paused or stopped, nor when its
-    /// cycles add up to no time, since no elapsed time can name a cycle
-    /// in that configuration.
+    /// A count-up cycle has no end for a tick to cross: ticks emit
+    /// [`TimerEvent::Running`] carrying the time it ran so far, until a
+    /// client ends it with [`Self::skip`]. A tick crossing into one
+    /// begins it where the cycle before it ended, however late it came.
+    ///
+    /// Has no effect when the timer is paused, waiting or stopped, nor
+    /// outside a count-up cycle when its cycles add up to no time, since
+    /// no elapsed time can name a cycle in that configuration.
     pub fn update(&mut self, now: u64) -> impl IntoIterator<Item = TimerEvent> {
         let mut events = Vec::with_capacity(3);
 
-        if let TimerState::Running = self.state {
-            let mut elapsed = self.elapsed(now);
+        if let TimerState::Overtime = self.state {
+            let overtime = self.overtime(now);
+
+            if overtime != self.cycle.duration {
+                self.cycle.duration = overtime;
+                events.push(TimerEvent::Overtime(self.cycle.clone()));
+            }
+        }
+
+        if let (TimerState::Running, Some(_)) = (&self.state, self.counting) {
+            let counted = self.counted(now);
+
+            if counted != self.cycle.duration {
+                self.cycle.duration = counted;
+                events.push(TimerEvent::Running(self.cycle.clone()));
+            }
 
-            let cycles = self.schedule.cumulated_cycles();
+            return events;
+        }
 
-            let Some(total_duration) = cycles.last().map(|cycle| cycle.duration) else {
+        if let TimerState::Running = self.state {
+            // NOTE: the floor lies in the cycle the timer was in as of
+            // the last tick, so it names the cycle a boundary ends.
+            let (Some(position), Some(prev)) = (self.position(now), self.locate(self.floor)) else {
                 return events;
             };
 
             if let TimerLoop::Fixed(loops) = self.schedule.loops
-                && elapsed >= total_duration * loops
+                && position.elapsed >= (position.total() * loops) as isize
             {
                 let mut ended_cycle = self.cycle.clone();
                 ended_cycle.duration = 0;
                 events.push(TimerEvent::Ended(ended_cycle));
+                events.push(TimerEvent::LoopEnded(loops - 1));
+                events.push(TimerEvent::Completed);
                 events.push(TimerEvent::Stopped);
                 self.reset();
                 return events;
             }
 
-            elapsed %= total_duration;
+            let next_cycle = position.cycle();
 
-            let last_cycle = cycles[cycles.len() - 1].clone();
-            let next_cycle = cycles
-                .into_iter()
-                .fold(None, |next_cycle, mut cycle| match next_cycle {
-                    None if elapsed < cycle.duration => {
-                        cycle.duration -= elapsed;
-                        Some(cycle)
-                    }
-                    _ => next_cycle,
-                })
-                .unwrap_or(last_cycle);
+            // NOTE: a boundary shows as the position moving on from the
+            // cycle the floor lies in, rather than as a different name,
+            // since a cycle repeating its predecessor's name, a single
+            // looping cycle and a cycle lengthened by its ratio would
+            // all hide it.
+            let began_cycle = (position.loops, position.index) != (prev.loops, prev.index);
 
-            // NOTE: a boundary shows as a different name, or as remaining
-            // time going back up, which is what a cycle repeating its
-            // predecessor's name looks like, and what a single looping
-            // cycle looks like every time it comes round.
-            let began_cycle =
-                self.cycle.name != next_cycle.name || next_cycle.duration > self.cycle.duration;
+            if began_cycle && self.cycle.overtime {
+                let mut prev_cycle = self.cycle.clone();
+                prev_cycle.duration = 0;
+                events.push(TimerEvent::Ended(prev_cycle));
 
-            if began_cycle {
+                if prev.is_last() {
+                    events.push(TimerEvent::LoopEnded(prev.loops));
+                }
+
+                // NOTE: the overtime counts from where the cycle ended
+                // rather than from where the tick landed, so a late tick
+                // loses none of it.
+                self.state = TimerState::Overtime;
+                self.floor = prev.end();
+                self.cycle.duration = self.overtime(now);
+                events.push(TimerEvent::Overtime(self.cycle.clone()));
+                return events;
+            }
+
+            if began_cycle && !self.cycle.auto_advance {
                 let mut prev_cycle = self.cycle.clone();
                 prev_cycle.duration = 0;
                 events.push(TimerEvent::Ended(prev_cycle));
-                events.push(TimerEvent::Began(next_cycle.clone()));
-            } else if self.cycle.duration != next_cycle.duration {
-                events.push(TimerEvent::Running(next_cycle.clone()));
+
+                if prev.is_last() {
+                    events.push(TimerEvent::LoopEnded(prev.loops));
+                }
+
+                // NOTE: the timer waits where the cycle ended rather than
+                // where the tick landed, so the next cycle begins whole
+                // whenever a client resumes it.
+                self.state = TimerState::Waiting;
+                let cycle = self.follow(now, &prev);
+                events.push(TimerEvent::Awaiting(cycle));
+                return events;
             }
 
-            self.cycle = next_cycle;
+            if began_cycle {
+                let mut prev_cycle = self.cycle.clone();
+                prev_cycle.duration = 0;
+                events.push(TimerEvent::Ended(prev_cycle));
+
+                if let Some((index, loops, begin)) = self.count_up_between(&prev, &position) {
+                    events.extend(crossed_loops(prev.loops, loops));
+                    self.loop_index = loops;
+                    self.counting = Some(index);
+                    self.floor = begin;
+                    self.enter(self.schedule.cycles[index].clone());
+                    self.cycle.duration = self.counted(now);
+                    events.push(TimerEvent::Began(self.cycle.clone()));
+                    return events;
+                }
+
+                events.extend(crossed_loops(prev.loops, position.loops));
+                self.loop_index = position.loops;
+
+                // NOTE: the floor follows the timer from cycle to cycle,
+                // which is what lets the next boundary find the cycle it
+                // ends.
+                self.floor = position.begin();
+                self.enter(next_cycle);
+                events.push(TimerEvent::Began(self.cycle.clone()));
+            } else {
+                let running = self.cycle.duration != next_cycle.duration;
+                self.stay(next_cycle);
+
+                if running {
+                    events.push(TimerEvent::Running(self.cycle.clone()));
+                }
+            }
         }
 
         events
     }
 
-    /// Starts the timer from the first configured cycle.
+    /// The instant, in Unix epoch milliseconds, at which
+    /// [`Self::update`] next has something to report, if any.
     ///
-    /// Has no effect if the timer is already running or paused.
-    pub fn start(&mut self, now: u64) -> impl IntoIterator<Item = TimerEvent> {
-        let mut events = Vec::with_capacity(2);
+    /// That is the end of the current cycle, or the next whole second of
+    /// the duration the cycle shows when `ticks` asks for the periodic
+    /// [`TimerEvent::Running`] and [`TimerEvent::Overtime`], whichever
+    /// comes first. A caller sleeping until then misses no boundary, and
+    /// wakes for nothing else.
+    ///
+    /// Returns `None` when only a client can change the timer: when it
+    /// is paused, waiting or stopped, and when it counts up or counts
+    /// overtime without `ticks`, since no boundary ends either.
+    pub fn deadline(&self, now: u64, ticks: bool) -> Option<u64> {
+        // NOTE: the next whole second counts from the duration the last
+        // tick reported rather than from the one the cycle shows now, so
+        // a tick waking late still has it due instead of aiming at the
+        // second after.
+        let reported = self.cycle.duration;
+        let next_second_up = |shown: usize| {
+            let target = (reported / 1000 + 1) * 1000;
+            now + target.saturating_sub(shown) as u64
+        };
+
+        match self.state {
+            TimerState::Running if self.counting.is_some() => {
+                ticks.then(|| next_second_up(self.counted(now)))
+            }
+            TimerState::Overtime => ticks.then(|| next_second_up(self.overtime(now))),
+            TimerState::Running => {
+                let remaining = self.left(now)?;
+                let target = if ticks {
+                    reported.saturating_sub(1) / 1000 * 1000
+                } else {
+                    0
+                };
+
+                Some(now + remaining.saturating_sub(target) as u64)
+            }
+            TimerState::Paused | TimerState::Waiting | TimerState::Stopped => None,
+        }
+    }
+
+    /// Brings the timer across a jump of its clock from `from` to `now`,
+    /// both in Unix epoch milliseconds, the way `jump` says.
+    ///
+    /// A jump forward is time the timer did not see pass, such as the
+    /// hours a suspended machine slept through. Whatever the policy,
+    /// the boundaries the timer reached by `from` are crossed first, at
+    /// the instant they happened, so none of them is lost to the jump.
+    ///
+    /// A jump backward is a clock set back, which never takes back the
+    /// time the timer counted: it runs on from where it was at `from`,
+    /// or pauses there under [`TimerJump::Pause`].
+    pub fn jump(
+        &mut self,
+        from: u64,
+        now: u64,
+        jump: TimerJump,
+    ) -> impl IntoIterator<Item = TimerEvent> {
+        let mut events = Vec::new();
+
+        let until = match jump {
+            TimerJump::Skip => now.max(from),
+            TimerJump::Pause | TimerJump::Count => from,
+        };
+
+        // NOTE: a deadline is the instant a tick would have crossed the
+        // boundary, so updating there reports it as that tick would
+        // have, and the next deadline is the next boundary.
+        let mut at = from;
+        while let Some(deadline) = self.deadline(at, false)
+            && deadline <= until
+        {
+            let len = events.len();
+            events.extend(self.update(deadline));
+
+            if events.len() == len {
+                break;
+            }
 
-        if matches!(self.state, TimerState::Stopped) {
-            self.state = TimerState::Running;
-            self.cycle = self.schedule.first_cycle();
-            self.started_at = Some(now);
-            self.elapsed = 0;
-            events.push(TimerEvent::Started);
-            events.push(TimerEvent::Began(self.cycle.clone()));
+            at = deadline;
+        }
+
+        match jump {
+            TimerJump::Pause => events.extend(self.pause(from).unwrap_or_default()),
+            _ if now < from => {
+                if let Some(started_at) = &mut self.started_at {
+                    *started_at = started_at.saturating_sub(from - now);
+                }
+            }
+            TimerJump::Count | TimerJump::Skip => events.extend(self.update(now)),
         }
 
         events
     }
 
-    /// Sets the remaining duration of the current cycle to
-    /// `duration_secs`.
-    ///
-    /// The current cycle is derived from the elapsed time rather than
-    /// stored, so this moves the elapsed time to the point that leaves
-    /// `duration_secs` remaining, and every later tick recomputes the
-    /// value it was given.
-    ///
-    /// A cycle cannot hold more than its configured length, since a
-    /// longer remaining duration would place the timeline inside the
-    /// previous cycle and rename the cycle under the caller. The
-    /// request is clamped to that length, and the returned event
-    /// carries the effective value.
+    /// Starts the timer from the first configured cycle, of the first
+    /// loop.
     ///
-    /// Has no effect if the timer is stopped, since [`Self::start`]
-    /// resets the elapsed time anyway.
-    pub fn set(&mut self, now: u64, duration_secs: usize) -> impl IntoIterator<Item = TimerEvent> {
-        if matches!(self.state, TimerState::Stopped) {
-            return None;
+    /// Fails with [`TimerError::AlreadyRunning`] if the timer is
+    /// running already, paused, waiting or counting overtime.
+    pub fn start(&mut self, now: u64) -> Result<Vec<TimerEvent>, TimerError> {
+        if !matches!(self.state, TimerState::Stopped) {
+            return Err(TimerError::AlreadyRunning);
         }
 
-        let cycles = self.schedule.cumulated_cycles();
-        let total_duration = cycles.last().map(|cycle| cycle.duration)?;
+        self.state = TimerState::Running;
+        self.enter(self.schedule.first_cycle());
+        self.started_at = Some(now);
+        self.elapsed = 0;
+        self.extended = 0;
+        self.floor = 0;
+        self.loop_index = 0;
 
-        let elapsed = self.elapsed(now);
-        let loops = elapsed / total_duration;
-        let elapsed_in_loop = elapsed % total_duration;
+        if self.cycle.count_up {
+            self.counting = Some(0);
+            self.cycle.duration = 0;
+        }
 
-        // NOTE: the cycle is found by position rather than by name,
-        // since a configuration is free to run the same name twice.
-        let index = cycles
-            .iter()
-            .position(|cycle| elapsed_in_loop < cycle.duration)
-            .unwrap_or(cycles.len() - 1);
+        Ok(vec![
+            TimerEvent::Started,
+            TimerEvent::LoopBegan(0),
+            TimerEvent::Began(self.cycle.clone()),
+        ])
+    }
+
+    /// Ends the current cycle and begins the next one.
+    ///
+    /// Moves the elapsed time to the end of the current cycle, then
+    /// reports the boundary exactly as a tick crossing it would:
+    /// [`TimerEvent::Ended`] then [`TimerEvent::Began`], the loop
+    /// events between them when the skipped cycle was the last one of
+    /// its loop, or [`TimerEvent::Ended`], [`TimerEvent::LoopEnded`],
+    /// [`TimerEvent::Completed`] then [`TimerEvent::Stopped`] when it
+    /// was the last one of the last configured loop, in which case the
+    /// timer resets as [`Self::stop`] would.
+    ///
+    /// A paused timer skips as well and stays paused, at the start of
+    /// the next cycle. A waiting timer, or one counting overtime, has no
+    /// current cycle left to end, so it begins the next one as
+    /// [`Self::resume`] does. Fails with [`TimerError::Stopped`] if the
+    /// timer is stopped, and with [`TimerError::NoTime`] when its cycles
+    /// add up to no time.
+    ///
+    /// Skipping is how a count-up cycle ends. [`TimerEvent::Ended`] then
+    /// carries the time it ran, which shifts the cycles after it as an
+    /// extension would, and the cycle after it lasts its share of that
+    /// time when it has a [`TimerCycle::ratio`].
+    pub fn skip(&mut self, now: u64) -> Result<Vec<TimerEvent>, TimerError> {
+        let mut events = Vec::with_capacity(2);
+
+        match self.state {
+            TimerState::Stopped => return Err(TimerError::Stopped),
+            TimerState::Waiting | TimerState::Overtime => return self.resume(now),
+            TimerState::Running | TimerState::Paused => (),
+        }
+
+        if let Some(index) = self.counting {
+            return Ok(self.end_count_up(now, index));
+        }
+
+        let position = self.position(now).ok_or(TimerError::NoTime)?;
+
+        let mut ended_cycle = self.cycle.clone();
+        ended_cycle.duration = 0;
+        events.push(TimerEvent::Ended(ended_cycle));
+
+        if position.is_last() {
+            events.push(TimerEvent::LoopEnded(position.loops));
+        }
+
+        if let TimerLoop::Fixed(loops) = self.schedule.loops
+            && position.is_last()
+            && position.loops + 1 >= loops
+        {
+            events.push(TimerEvent::Completed);
+            events.push(TimerEvent::Stopped);
+            self.reset();
+            return Ok(events);
+        }
+
+        if position.is_last() {
+            self.loop_index = position.loops + 1;
+            events.push(TimerEvent::LoopBegan(self.loop_index));
+        }
+
+        self.seek(now, position.end());
+
+        // NOTE: the next cycle is read from the schedule rather than
+        // derived from the new elapsed time, since a zero-length cycle
+        // would otherwise be skipped along with the current one.
+        let next = (position.index + 1) % position.cycles.len();
+        self.enter(self.schedule.cycles[next].clone());
+
+        if self.cycle.count_up {
+            self.counting = Some(next);
+            self.cycle.duration = 0;
+        }
+
+        events.push(TimerEvent::Began(self.cycle.clone()));
+
+        Ok(events)
+    }
+
+    /// Ends the count-up cycle at `index` and begins the one after it.
+    fn end_count_up(&mut self, now: u64, index: usize) -> Vec<TimerEvent> {
+        let mut events = Vec::with_capacity(2);
+
+        let counted = self.counted(now);
+        let mut ended_cycle = self.cycle.clone();
+        ended_cycle.duration = counted;
+        events.push(TimerEvent::Ended(ended_cycle));
+
+        let cycles = self.schedule.cumulated_cycles();
+        let last = index == cycles.len() - 1;
+
+        if last {
+            events.push(TimerEvent::LoopEnded(self.loop_index));
+        }
+
+        if let TimerLoop::Fixed(loops) = self.schedule.loops
+            && last
+            && self.loop_index + 1 >= loops
+        {
+            events.push(TimerEvent::Completed);
+            events.push(TimerEvent::Stopped);
+            self.reset();
+            return events;
+        }
+
+        if last {
+            self.loop_index += 1;
+            events.push(TimerEvent::LoopBegan(self.loop_index));
+        }
+
+        // NOTE: the cycle ran no schedule time, so the time it counted
+        // becomes an extension, which leaves the schedule time at the
+        // floor: where the next cycle begins.
+        self.extended += counted as isize;
+        self.counting = None;
+
+        let next = (index + 1) % cycles.len();
+        self.enter(self.schedule.cycles[next].clone());
+
+        if self.cycle.count_up {
+            self.counting = Some(next);
+            self.cycle.duration = 0;
+        } else {
+            let duration = self.cycle.derived_duration(counted);
+            self.extended += duration as isize - self.cycle.duration as isize;
+            self.cycle.duration = duration;
+        }
+
+        events.push(TimerEvent::Began(self.cycle.clone()));
+        events
+    }
+
+    /// Moves the timer to the start of the cycle at `index`, in the loop
+    /// `loop_index` or in the current one when none is given.
+    ///
+    /// The elapsed time is recomputed against the cumulated cycles so it
+    /// lands at the start of that cycle, which is all the timer needs:
+    /// every later tick derives the cycle from it. The cycle left behind
+    /// is reported as [`TimerEvent::Ended`] with the time it still had,
+    /// as [`Self::stop`] does, then the target as
+    /// [`TimerEvent::Began`].
+    ///
+    /// A stopped timer starts there, emitting [`TimerEvent::Started`]
+    /// then [`TimerEvent::Began`], and counts from the first loop unless
+    /// told otherwise. A paused timer moves and stays paused. A timer
+    /// waiting or counting overtime ended its cycle already, so it only
+    /// begins the target. Moving to another loop, or starting, emits
+    /// [`TimerEvent::LoopBegan`] right before [`TimerEvent::Began`],
+    /// while the loop left behind is abandoned rather than ended.
+    ///
+    /// Fails with [`TimerError::OutOfSchedule`] when `index` is out of
+    /// the schedule or `loop_index` beyond the configured loops, or
+    /// further than the timer can count, and
+    /// with [`TimerError::NoTime`] when the cycles add up to no time and
+    /// the target is not a count-up cycle.
+    pub fn goto(
+        &mut self,
+        now: u64,
+        index: usize,
+        loop_index: Option<usize>,
+    ) -> Result<Vec<TimerEvent>, TimerError> {
+        let mut events = Vec::with_capacity(2);
+
+        let cycles = self.schedule.cumulated_cycles();
+
+        if index >= cycles.len() {
+            return Err(TimerError::OutOfSchedule);
+        }
+
+        let total_duration = cycles[cycles.len() - 1].duration;
+
+        if total_duration == 0 && !cycles[index].count_up {
+            return Err(TimerError::NoTime);
+        }
+
+        let loop_index = match (loop_index, &self.state, self.position(now)) {
+            (Some(loop_index), _, _) => loop_index,
+            (None, TimerState::Stopped, _) => 0,
+            (None, _, None) => self.loop_index,
+            (None, _, Some(position)) => position.loops,
+        };
+
+        if let TimerLoop::Fixed(loops) = self.schedule.loops
+            && loop_index >= loops
+        {
+            return Err(TimerError::OutOfSchedule);
+        }
 
-        let end = cycles[index].duration;
         let begin = index.checked_sub(1).map_or(0, |prev| cycles[prev].duration);
-        let duration_secs = duration_secs.min(end - begin);
+        let target = loop_index
+            .checked_mul(total_duration)
+            .and_then(|loop_begin| loop_begin.checked_add(begin))
+            .filter(|target| isize::try_from(*target).is_ok())
+            .ok_or(TimerError::OutOfSchedule)?;
+
+        // NOTE: a cycle a waiting timer or one counting overtime ended
+        // last in its loop ended the loop along with it, so the timer
+        // is in none until it begins another.
+        let current_loop = match self.state {
+            TimerState::Stopped => None,
+            TimerState::Running | TimerState::Paused => Some(self.loop_index),
+            TimerState::Waiting | TimerState::Overtime => self
+                .floor
+                .checked_sub(1)
+                .and_then(|lookup| self.locate(lookup))
+                .filter(|prev| !prev.is_last())
+                .map(|prev| prev.loops),
+        };
+
+        match self.state {
+            TimerState::Stopped => {
+                self.state = TimerState::Running;
+                self.elapsed = 0;
+                self.extended = 0;
+                events.push(TimerEvent::Started);
+            }
+            TimerState::Running | TimerState::Paused => {
+                events.push(TimerEvent::Ended(self.cycle.clone()));
+            }
+            TimerState::Waiting | TimerState::Overtime => {
+                self.state = TimerState::Running;
+            }
+        }
 
-        self.cycle = TimerCycle::new(&cycles[index].name, duration_secs);
-        self.elapsed = loops * total_duration + (end - duration_secs);
-        self.started_at = matches!(self.state, TimerState::Running).then_some(now);
+        if current_loop != Some(loop_index) {
+            events.push(TimerEvent::LoopBegan(loop_index));
+        }
+
+        self.seek(now, target);
+        self.enter(self.schedule.cycles[index].clone());
+        self.loop_index = loop_index;
+        self.counting = None;
+
+        if self.cycle.count_up {
+            self.counting = Some(index);
+            self.cycle.duration = 0;
+        }
+
+        events.push(TimerEvent::Began(self.cycle.clone()));
+
+        Ok(events)
+    }
+
+    /// Sets the remaining duration of the current cycle to `duration`
+    /// milliseconds.
+    ///
+    /// The current cycle is derived from the elapsed time rather than
+    /// stored, so this moves the elapsed time to the point that leaves
+    /// `duration` remaining, and every later tick recomputes the value
+    /// it was given.
+    ///
+    /// A cycle cannot hold more than its configured length, since a
+    /// longer remaining duration would place the timeline inside the
+    /// previous cycle and rename the cycle under the caller. The
+    /// request is clamped to that length, and the returned event
+    /// carries the effective value. Lengthening a cycle beyond that is
+    /// what [`Self::extend`] is for.
+    ///
+    /// Fails with [`TimerError::Stopped`] if the timer is stopped,
+    /// since [`Self::start`] resets the elapsed time anyway, with
+    /// [`TimerError::NotRunning`] if it is waiting or counting overtime,
+    /// since no cycle is running then, and with
+    /// [`TimerError::CountingUp`] in a count-up cycle, which has no
+    /// duration to set.
+    pub fn set(&mut self, now: u64, duration: usize) -> Result<Vec<TimerEvent>, TimerError> {
+        let position = self.adjustable(now)?;
+        let end = position.end();
+        let duration = duration.min(end - position.begin());
+
+        self.stay(position.cycle());
+        self.cycle.duration = duration;
+        self.seek(now, end - duration);
+
+        // NOTE: a duration of zero seeks to where the next cycle begins,
+        // and the floor has to stay in this one for the next tick to
+        // see it end.
+        self.floor = position.begin();
+
+        Ok(vec![TimerEvent::Set(self.cycle.clone())])
+    }
+
+    /// Extends the current cycle by `delta` milliseconds, or shortens it
+    /// when negative.
+    ///
+    /// Unlike [`Self::set`], the extra time is not bound by the
+    /// configured length of the cycle. It is kept in
+    /// [`Self::extended`], which shifts the schedule rather than the
+    /// elapsed time: every cycle after this one starts that much later,
+    /// and keeps its configured duration.
+    ///
+    /// Shortening stops at the end of the cycle, so a delta larger than
+    /// what remains ends the cycle on the next tick rather than eating
+    /// into the following ones. The returned event carries the new
+    /// remaining duration.
+    ///
+    /// Fails as [`Self::set`] does, with [`TimerError::NoTime`] when
+    /// its cycles add up to no time, and with
+    /// [`TimerError::OutOfRange`] when the cycle would last longer than
+    /// the timer can count, leaving the timer as it was.
+    pub fn extend(&mut self, now: u64, delta: isize) -> Result<Vec<TimerEvent>, TimerError> {
+        let position = self.adjustable(now)?;
+        let remaining = position.remaining();
+        let delta = delta.max(-(remaining as isize));
+
+        // NOTE: the remaining time is derived from the extension, so
+        // both have to hold the delta for the next tick to compute it.
+        let (Some(extended), Some(duration)) = (
+            self.extended.checked_add(delta),
+            (remaining as isize).checked_add(delta),
+        ) else {
+            return Err(TimerError::OutOfRange);
+        };
+
+        self.extended = extended;
+        self.floor = position.begin();
+        self.stay(position.cycle());
+        self.cycle.duration = duration as usize;
+
+        Ok(vec![TimerEvent::Extended(self.cycle.clone())])
+    }
+
+    /// The position of the cycle [`Self::set`] and [`Self::extend`]
+    /// act on, or why there is none.
+    fn adjustable(&self, now: u64) -> Result<TimerPosition, TimerError> {
+        match self.state {
+            TimerState::Stopped => return Err(TimerError::Stopped),
+            TimerState::Waiting | TimerState::Overtime => return Err(TimerError::NotRunning),
+            TimerState::Running | TimerState::Paused => (),
+        }
+
+        if self.counting.is_some() {
+            return Err(TimerError::CountingUp);
+        }
 
-        Some(TimerEvent::Set(self.cycle.clone()))
+        self.position(now).ok_or(TimerError::NoTime)
     }
 
     /// Pauses the timer, saving the elapsed time.
     ///
-    /// Has no effect if the timer is not running.
-    pub fn pause(&mut self, now: u64) -> impl IntoIterator<Item = TimerEvent> {
-        if matches!(self.state, TimerState::Running) {
-            self.elapsed = self.elapsed(now);
-            self.started_at = None;
-            self.state = TimerState::Paused;
-            Some(TimerEvent::Paused(self.cycle.clone()))
-        } else {
-            None
+    /// Fails with [`TimerError::Stopped`] if the timer is stopped, and
+    /// with [`TimerError::NotRunning`] if it is paused, waiting or
+    /// counting overtime.
+    pub fn pause(&mut self, now: u64) -> Result<Vec<TimerEvent>, TimerError> {
+        match self.state {
+            TimerState::Stopped => return Err(TimerError::Stopped),
+            TimerState::Paused | TimerState::Waiting | TimerState::Overtime => {
+                return Err(TimerError::NotRunning);
+            }
+            TimerState::Running => (),
         }
+
+        // NOTE: ticks may have stopped short of now, so the cycle pauses
+        // with what it shows now rather than as of the last tick.
+        self.cycle.duration = match self.counting {
+            Some(_) => self.counted(now),
+            None => self.left(now).unwrap_or(self.cycle.duration),
+        };
+        self.elapsed = self.elapsed(now);
+        self.started_at = None;
+        self.state = TimerState::Paused;
+
+        Ok(vec![TimerEvent::Paused(self.cycle.clone())])
     }
 
     /// Resumes the timer from where it was paused.
     ///
-    /// Has no effect if the timer is not paused.
-    pub fn resume(&mut self, now: u64) -> impl IntoIterator<Item = TimerEvent> {
-        if matches!(self.state, TimerState::Paused) {
-            self.state = TimerState::Running;
-            self.started_at = Some(now);
-            Some(TimerEvent::Resumed(self.cycle.clone()))
-        } else {
-            None
+    /// A waiting timer begins the cycle it awaits instead, emitting
+    /// [`TimerEvent::Began`] as a tick would have without the wait.
+    ///
+    /// A timer counting overtime acknowledges it and begins the next
+    /// cycle the same way. The overtime is kept in [`Self::extended`],
+    /// as if the cycle had been extended by that much, so the schedule
+    /// records how long the cycle actually lasted.
+    ///
+    /// Either begins the next loop, emitting [`TimerEvent::LoopBegan`]
+    /// first, when the cycle that ended was the last one of its loop.
+    ///
+    /// Fails with [`TimerError::AlreadyRunning`] if the timer is
+    /// running, and with [`TimerError::Stopped`] if it is stopped.
+    pub fn resume(&mut self, now: u64) -> Result<Vec<TimerEvent>, TimerError> {
+        let mut events = Vec::with_capacity(2);
+
+        // NOTE: the cycle a waiting timer ended, like the one a timer
+        // counts overtime past, ends at the floor, so the millisecond
+        // before it names that cycle.
+        let prev = self
+            .floor
+            .checked_sub(1)
+            .and_then(|lookup| self.locate(lookup));
+
+        let next_loop = prev
+            .as_ref()
+            .filter(|prev| prev.is_last())
+            .map(|prev| prev.loops + 1);
+
+        match self.state {
+            TimerState::Paused => events.push(TimerEvent::Resumed(self.cycle.clone())),
+            TimerState::Waiting => {
+                if let Some(next_loop) = next_loop {
+                    self.loop_index = next_loop;
+                    events.push(TimerEvent::LoopBegan(next_loop));
+                }
+
+                events.push(TimerEvent::Began(self.cycle.clone()));
+            }
+            TimerState::Overtime => {
+                let prev = prev.ok_or(TimerError::NoTime)?;
+
+                self.extended += self.overtime(now) as isize;
+                self.state = TimerState::Running;
+                if let Some(next_loop) = next_loop {
+                    self.loop_index = next_loop;
+                    events.push(TimerEvent::LoopBegan(next_loop));
+                }
+
+                events.push(TimerEvent::Began(self.follow(now, &prev)));
+                return Ok(events);
+            }
+            TimerState::Running => return Err(TimerError::AlreadyRunning),
+            TimerState::Stopped => return Err(TimerError::Stopped),
+        }
+
+        self.state = TimerState::Running;
+        self.started_at = Some(now);
+        Ok(events)
+    }
+
+    /// Labels the timer with the task it runs for, `task` and `tags`
+    /// replacing whatever it carried.
+    ///
+    /// The current cycle carries them, and every cycle after it, until
+    /// the timer stops. A stopped timer keeps them for the run the next
+    /// [`Self::start`] begins, and reports nothing, since no cycle runs
+    /// yet. Otherwise emits [`TimerEvent::Labeled`]. Never fails, since
+    /// a timer in any state can carry a label.
+    pub fn label(
+        &mut self,
+        task: Option<String>,
+        tags: Vec<String>,
+    ) -> Result<Vec<TimerEvent>, TimerError> {
+        self.cycle.task = task;
+        self.cycle.tags = tags;
+
+        match self.state {
+            TimerState::Stopped => Ok(Vec::new()),
+            _ => Ok(vec![TimerEvent::Labeled(self.cycle.clone())]),
+        }
+    }
+
+    /// Records an interruption of the current cycle.
+    ///
+    /// Emits [`TimerEvent::Interrupted`]. Fails with
+    /// [`TimerError::Stopped`] if the timer is stopped, and with
+    /// [`TimerError::NotRunning`] if it is waiting, since no cycle is
+    /// under way then.
+    pub fn interrupt(
+        &mut self,
+        kind: TimerInterruptionKind,
+        note: Option<String>,
+    ) -> Result<Vec<TimerEvent>, TimerError> {
+        match self.state {
+            TimerState::Stopped => return Err(TimerError::Stopped),
+            TimerState::Waiting => return Err(TimerError::NotRunning),
+            TimerState::Running | TimerState::Paused | TimerState::Overtime => (),
         }
+
+        self.cycle
+            .interruptions
+            .push(TimerInterruption { kind, note });
+
+        Ok(vec![TimerEvent::Interrupted(self.cycle.clone())])
     }
 
     /// Stops the timer and resets it to the initial state.
     ///
     /// Acts on a paused timer as well as on a running one, since a
-    /// paused timer that cannot be stopped can only be left behind.
-    /// Has no effect if the timer is already stopped.
-    pub fn stop(&mut self) -> impl IntoIterator<Item = TimerEvent> {
+    /// paused timer that cannot be stopped can only be left behind. A
+    /// timer waiting or counting overtime ended its cycle already, so it
+    /// only reports [`TimerEvent::Stopped`]. Fails with
+    /// [`TimerError::Stopped`] if the timer is already stopped.
+    pub fn stop(&mut self) -> Result<Vec<TimerEvent>, TimerError> {
         let mut events = Vec::with_capacity(2);
 
-        if !matches!(self.state, TimerState::Stopped) {
-            events.push(TimerEvent::Ended(self.cycle.clone()));
-            events.push(TimerEvent::Stopped);
-            self.reset();
+        match self.state {
+            TimerState::Stopped => return Err(TimerError::Stopped),
+            TimerState::Waiting | TimerState::Overtime => (),
+            TimerState::Running | TimerState::Paused => {
+                events.push(TimerEvent::Ended(self.cycle.clone()));
+            }
         }
 
-        events
+        events.push(TimerEvent::Stopped);
+        self.reset();
+
+        Ok(events)
+    }
+
+    /// The schedule time: the elapsed time shifted back by the
+    /// extensions.
+    ///
+    /// Negative when the first cycle was extended by more than the
+    /// timer ran so far.
+    fn schedule_elapsed(&self, now: u64) -> isize {
+        self.elapsed(now) as isize - self.extended
+    }
+
+    /// The milliseconds left before the cycle the floor lies in ends,
+    /// which is none once the clock passed its end.
+    ///
+    /// The floor lies in the cycle the last tick left the timer in, so
+    /// this counts down to the boundary the next tick has to cross even
+    /// when the clock already named the cycle after it.
+    fn left(&self, now: u64) -> Option<usize> {
+        let end = self.locate(self.floor)?.end() as isize;
+        Some((end - self.schedule_elapsed(now)).max(0) as usize)
+    }
+
+    /// The milliseconds counted past the end of the cycle a timer in
+    /// [`TimerState::Overtime`] ended, which is where its floor stands.
+    fn overtime(&self, now: u64) -> usize {
+        self.schedule_elapsed(now)
+            .saturating_sub(self.floor as isize)
+            .max(0) as usize
+    }
+
+    /// The milliseconds a count-up cycle ran so far, counted from the
+    /// floor where it began.
+    fn counted(&self, now: u64) -> usize {
+        self.schedule_elapsed(now)
+            .saturating_sub(self.floor as isize)
+            .max(0) as usize
+    }
+
+    /// Makes `cycle` the current one, carrying the task the timer runs
+    /// for over to it but none of the interruptions of the last one.
+    fn enter(&mut self, cycle: TimerCycle) {
+        self.cycle.cycle = cycle;
+        self.cycle.interruptions.clear();
+    }
+
+    /// Refreshes the current cycle with `cycle`, the same one as of
+    /// another time, keeping what the timer recorded during it.
+    fn stay(&mut self, cycle: TimerCycle) {
+        self.cycle.cycle = cycle;
+    }
+
+    /// Moves the timer to where `prev` ends, in the cycle following it,
+    /// and returns that cycle whole.
+    fn follow(&mut self, now: u64, prev: &TimerPosition) -> TimerCycleRun {
+        let next = (prev.index + 1) % prev.cycles.len();
+
+        self.seek(now, prev.end());
+        self.enter(self.schedule.cycles[next].clone());
+        self.counting = None;
+
+        if self.cycle.count_up {
+            self.counting = Some(next);
+            self.cycle.duration = 0;
+        }
+
+        self.cycle.clone()
+    }
+
+    /// The first count-up cycle between `prev` and `next`, with the
+    /// loop it falls in and the schedule time it begins at, if a tick
+    /// crossing from one to the other passed one.
+    fn count_up_between(
+        &self,
+        prev: &TimerPosition,
+        next: &TimerPosition,
+    ) -> Option<(usize, usize, usize)> {
+        let (mut loops, mut index) = (prev.loops, prev.index);
+
+        loop {
+            index += 1;
+
+            if index == prev.cycles.len() {
+                index = 0;
+                loops += 1;
+            }
+
+            if (loops, index) >= (next.loops, next.index) {
+                return None;
+            }
+
+            if prev.cycles[index].count_up {
+                let begin = index.checked_sub(1).map_or(0, |i| prev.cycles[i].duration);
+                return Some((index, loops, loops * prev.total() + begin));
+            }
+        }
+    }
+
+    /// Moves the elapsed time so the schedule time reads
+    /// `schedule_elapsed` at `now`, and looks the cycle up from there.
+    ///
+    /// A schedule time earlier than what shortened cycles skipped
+    /// cannot be reached by the elapsed time alone, so the shortening
+    /// it would undo is dropped instead.
+    fn seek(&mut self, now: u64, schedule_elapsed: usize) {
+        match schedule_elapsed.checked_add_signed(self.extended) {
+            Some(elapsed) => self.elapsed = elapsed,
+            None => {
+                self.elapsed = 0;
+                self.extended = -(schedule_elapsed as isize);
+            }
+        }
+
+        self.floor = schedule_elapsed;
+        self.started_at = matches!(self.state, TimerState::Running).then_some(now);
+    }
+
+    /// Locates the schedule time in the schedule.
+    ///
+    /// A count-up cycle holds the schedule time at the floor, where it
+    /// began, until it ends. Returns `None` when the cycles add up to no
+    /// time, since no elapsed time can name a cycle then.
+    fn position(&self, now: u64) -> Option<TimerPosition> {
+        let elapsed = self.schedule_elapsed(now);
+
+        let lookup = match self.counting {
+            Some(_) => self.floor,
+            None => elapsed.max(self.floor as isize) as usize,
+        };
+
+        self.locate_at(lookup, elapsed)
+    }
+
+    /// Locates the schedule time `lookup` in the schedule.
+    fn locate(&self, lookup: usize) -> Option<TimerPosition> {
+        self.locate_at(lookup, lookup as isize)
+    }
+
+    /// Locates the schedule time `lookup` in the schedule, on behalf of
+    /// the schedule time `elapsed` the position then measures from.
+    fn locate_at(&self, lookup: usize, elapsed: isize) -> Option<TimerPosition> {
+        let cycles = self.schedule.cumulated_cycles();
+        let total_duration = cycles.last().map(|cycle| cycle.duration)?;
+
+        if total_duration == 0 {
+            return None;
+        }
+
+        let elapsed_in_loop = lookup % total_duration;
+
+        // NOTE: the cycle is found by position rather than by name,
+        // since a configuration is free to run the same name twice.
+        let index = cycles
+            .iter()
+            .position(|cycle| elapsed_in_loop < cycle.duration)
+            .unwrap_or(cycles.len() - 1);
+
+        Some(TimerPosition {
+            elapsed,
+            loops: lookup / total_duration,
+            index,
+            cycles,
+        })
     }
 
     /// Returns the timer to the state a fresh one is in, keeping no
     /// elapsed time and no progress through the cycles.
     fn reset(&mut self) {
         self.state = TimerState::Stopped;
-        self.cycle = self.schedule.first_cycle();
+        self.cycle = self.schedule.first_cycle().into();
         self.started_at = None;
         self.elapsed = 0;
+        self.extended = 0;
+        self.floor = 0;
+        self.loop_index = 0;
+        self.counting = None;
+    }
+}
+
+/// Where a schedule time stands in a schedule: how many full loops it
+/// completed, and which cycle of the current loop it falls in.
+struct TimerPosition {
+    /// The schedule time the position was found for.
+    elapsed: isize,
+    /// The number of full loops completed.
+    loops: usize,
+    /// The index of the current cycle.
+    index: usize,
+    /// The cumulated cycles the position was found in.
+    cycles: Vec<TimerCycle>,
+}
+
+impl TimerPosition {
+    /// The current cycle, carrying its remaining duration.
+    fn cycle(&self) -> TimerCycle {
+        let mut cycle = self.cycles[self.index].clone();
+        cycle.duration = self.remaining();
+        cycle
+    }
+
+    /// The schedule time at which the current cycle begins.
+    fn begin(&self) -> usize {
+        let begin = self
+            .index
+            .checked_sub(1)
+            .map_or(0, |prev| self.cycles[prev].duration);
+        self.loops * self.total() + begin
+    }
+
+    /// The schedule time at which the current cycle ends.
+    fn end(&self) -> usize {
+        self.loops * self.total() + self.cycles[self.index].duration
     }
+
+    /// The time left before the current cycle ends, which exceeds its
+    /// configured length when an extension put the schedule time
+    /// before its start.
+    fn remaining(&self) -> usize {
+        (self.end() as isize - self.elapsed) as usize
+    }
+
+    /// The duration of a full loop.
+    fn total(&self) -> usize {
+        self.cycles[self.cycles.len() - 1].duration
+    }
+
+    /// Whether the current cycle is the last one of its loop.
+    fn is_last(&self) -> bool {
+        self.index == self.cycles.len() - 1
+    }
+}
+
+/// The loop events of a boundary from loop `from` into loop `to`: the
+/// end of the one left then the beginning of the one entered, if they
+/// differ.
+///
+/// A tick late enough to cross several loops reports the one it left
+/// and the one it landed in, as it reports only the cycles it left and
+/// landed in.
+fn crossed_loops(from: usize, to: usize) -> Vec<TimerEvent> {
+    if from == to {
+        return Vec::new();
+    }
+
+    vec![TimerEvent::LoopEnded(from), TimerEvent::LoopBegan(to)]
 }
 
 impl Eq for Timer {}
@@ -408,7 +2035,65 @@ impl PartialEq for Timer {
     }
 }
 
+/// How far a day went towards a daily goal.
+///
+/// A goal counts the cycles of one name that ran out during the day,
+/// and the time they ran. It is reached once every target it sets is
+/// met, and a goal setting none only keeps count.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TimerGoalProgress {
+    /// The name of the cycles the goal counts.
+    pub cycle: String,
+    /// Unix epoch milliseconds at which the day counted began.
+    pub since_ms: u64,
+    /// How many of those cycles ran out during the day.
+    pub cycles: usize,
+    /// How many of them the goal asks for, if it counts cycles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_cycles: Option<usize>,
+    /// The milliseconds they ran during the day, their pauses left out.
+    pub duration_ms: usize,
+    /// The milliseconds the goal asks for, if it counts time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_duration_ms: Option<usize>,
+    /// Whether every target was met.
+    pub reached: bool,
+}
+
+impl TimerGoalProgress {
+    /// Whether the counts meet every target the goal sets, given it
+    /// sets one.
+    pub fn meets_targets(&self) -> bool {
+        let targets = [
+            self.target_cycles.map(|target| self.cycles >= target),
+            self.target_duration_ms
+                .map(|target| self.duration_ms >= target),
+        ];
+
+        targets.iter().any(Option::is_some) && targets.into_iter().flatten().all(|met| met)
+    }
+}
+
+/// What a timer does with the time its clock jumped over, such as the
+/// hours a suspended machine slept through.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimerJump {
+    /// The timer pauses where the jump began, so the time jumped over
+    /// counts for nothing.
+    Pause,
+    /// The time jumped over counts, and the timer lands in the cycle the
+    /// clock names, reporting only the boundary into it.
+    #[default]
+    Count,
+    /// The time jumped over counts, and every boundary it crossed is
+    /// reported, as ticks would have reported them without the jump.
+    Skip,
+}
+
 /// Display precision for the remaining cycle duration.
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub enum TimerPrecision {
@@ -424,9 +2109,27 @@ pub enum TimerPrecision {
     Hour,
 }
 
+impl TimerPrecision {
+    /// The part of `duration`, in milliseconds, shown at this precision.
+    ///
+    /// Under a minute, every precision shows the seconds, and under an
+    /// hour, the hour precision shows the minutes, so two durations
+    /// truncating alike read alike.
+    pub fn truncate(&self, duration: usize) -> usize {
+        let unit = match self {
+            _ if duration < 60_000 => 1000,
+            Self::Second => 1000,
+            Self::Hour if duration >= 3_600_000 => 3_600_000,
+            Self::Minute | Self::Hour => 60_000,
+        };
+
+        duration / unit * unit
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use alloc::vec;
+    use alloc::{string::ToString, vec};
 
     use crate::timer::*;
 
@@ -441,7 +2144,7 @@ mod tests {
                 ..Default::default()
             },
             state: TimerState::Running,
-            cycle: TimerCycle::new("a", 3),
+            cycle: TimerCycle::new("a", 3).into(),
             started_at: Some(0),
             ..Default::default()
         }
@@ -452,23 +2155,23 @@ mod tests {
         let mut timer = testing_timer();
 
         assert_eq!(timer.state, TimerState::Running);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 3));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
 
         timer.update(2);
         assert_eq!(timer.state, TimerState::Running);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 1));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 1));
 
         timer.update(3);
         assert_eq!(timer.state, TimerState::Running);
-        assert_eq!(timer.cycle, TimerCycle::new("b", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
 
         timer.update(5);
         assert_eq!(timer.state, TimerState::Running);
-        assert_eq!(timer.cycle, TimerCycle::new("c", 1));
+        assert_eq!(*timer.cycle, TimerCycle::new("c", 1));
 
         timer.update(6);
         assert_eq!(timer.state, TimerState::Running);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 3));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
     }
 
     #[test]
@@ -487,11 +2190,11 @@ mod tests {
         assert_eq!(
             events,
             vec![
-                TimerEvent::Running(TimerCycle::new("a", 2)),
-                TimerEvent::Running(TimerCycle::new("a", 1)),
-                TimerEvent::Ended(TimerCycle::new("a", 0)),
-                TimerEvent::Began(TimerCycle::new("b", 2)),
-                TimerEvent::Running(TimerCycle::new("b", 1)),
+                TimerEvent::Running(TimerCycle::new("a", 2).into()),
+                TimerEvent::Running(TimerCycle::new("a", 1).into()),
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("b", 2).into()),
+                TimerEvent::Running(TimerCycle::new("b", 1).into()),
             ]
         );
     }
@@ -528,38 +2231,39 @@ mod tests {
         let mut events = Vec::new();
 
         assert_eq!(timer.state, TimerState::Stopped);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 3));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
 
-        events.extend(timer.start(0));
-        events.extend(timer.set(0, 2));
+        events.extend(timer.start(0).unwrap());
+        events.extend(timer.set(0, 2).unwrap());
 
         assert_eq!(timer.state, TimerState::Running);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
 
-        events.extend(timer.pause(0));
+        events.extend(timer.pause(0).unwrap());
 
         assert_eq!(timer.state, TimerState::Paused);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
 
-        events.extend(timer.resume(0));
+        events.extend(timer.resume(0).unwrap());
 
         assert_eq!(timer.state, TimerState::Running);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
 
-        events.extend(timer.stop());
+        events.extend(timer.stop().unwrap());
 
         assert_eq!(timer.state, TimerState::Stopped);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 3));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
 
         assert_eq!(
             events,
             vec![
                 TimerEvent::Started,
-                TimerEvent::Began(TimerCycle::new("a", 3)),
-                TimerEvent::Set(TimerCycle::new("a", 2)),
-                TimerEvent::Paused(TimerCycle::new("a", 2)),
-                TimerEvent::Resumed(TimerCycle::new("a", 2)),
-                TimerEvent::Ended(TimerCycle::new("a", 2)),
+                TimerEvent::LoopBegan(0),
+                TimerEvent::Began(TimerCycle::new("a", 3).into()),
+                TimerEvent::Set(TimerCycle::new("a", 2).into()),
+                TimerEvent::Paused(TimerCycle::new("a", 2).into()),
+                TimerEvent::Resumed(TimerCycle::new("a", 2).into()),
+                TimerEvent::Ended(TimerCycle::new("a", 2).into()),
                 TimerEvent::Stopped,
             ]
         );
@@ -569,67 +2273,73 @@ mod tests {
     fn a_set_duration_survives_the_next_ticks() {
         let mut timer = testing_timer();
 
-        timer.set(0, 2);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        timer.set(0, 2).unwrap();
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
 
         // The cycle is derived from the elapsed time, so the tick that
         // used to discard the set duration now recomputes it.
         timer.update(0);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
 
         timer.update(1);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 1));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 1));
 
         timer.update(2);
-        assert_eq!(timer.cycle, TimerCycle::new("b", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
     }
 
     #[test]
     fn a_tick_changing_nothing_says_nothing() {
         let mut timer = testing_timer();
 
-        timer.set(0, 2);
+        timer.set(0, 2).unwrap();
 
-        // The tick lands in the same second as the set, so it recomputes
-        // the duration the set already announced.
+        // The tick lands in the same millisecond as the set, so it
+        // recomputes the duration the set already announced.
         assert_eq!(timer.update(0).into_iter().count(), 0);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
 
         let events: Vec<_> = timer.update(1).into_iter().collect();
-        assert_eq!(events, vec![TimerEvent::Running(TimerCycle::new("a", 1))]);
+        assert_eq!(
+            events,
+            vec![TimerEvent::Running(TimerCycle::new("a", 1).into())]
+        );
     }
 
     #[test]
     fn a_set_duration_is_clamped_to_the_cycle_length() {
         let mut timer = testing_timer();
         timer.update(2);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 1));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 1));
 
         // Cycle "a" lasts 3, so asking for 10 restarts it instead of
         // rewinding into the cycle before it, and the event says so.
-        let events: Vec<_> = timer.set(2, 10).into_iter().collect();
+        let events = timer.set(2, 10).unwrap();
 
-        assert_eq!(events, vec![TimerEvent::Set(TimerCycle::new("a", 3))]);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 3));
+        assert_eq!(
+            events,
+            vec![TimerEvent::Set(TimerCycle::new("a", 3).into())]
+        );
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
 
         timer.update(3);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
     }
 
     #[test]
     fn setting_a_cycle_to_zero_ends_it() {
         let mut timer = testing_timer();
 
-        timer.set(0, 0);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 0));
+        timer.set(0, 0).unwrap();
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 0));
 
         let events: Vec<_> = timer.update(0).into_iter().collect();
 
         assert_eq!(
             events,
             vec![
-                TimerEvent::Ended(TimerCycle::new("a", 0)),
-                TimerEvent::Began(TimerCycle::new("b", 2)),
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("b", 2).into()),
             ]
         );
     }
@@ -637,29 +2347,29 @@ mod tests {
     #[test]
     fn a_set_duration_holds_across_a_pause() {
         let mut timer = testing_timer();
-        timer.pause(1);
+        timer.pause(1).unwrap();
 
-        timer.set(1, 2);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        timer.set(1, 2).unwrap();
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
         assert_eq!(timer.started_at, None);
 
-        // Resuming ten seconds later resumes what was set, rather than
-        // counting the pause as elapsed time.
-        timer.resume(11);
+        // Resuming ten milliseconds later resumes what was set, rather
+        // than counting the pause as elapsed time.
+        timer.resume(11).unwrap();
         timer.update(11);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
 
         timer.update(12);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 1));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 1));
     }
 
     #[test]
-    fn setting_a_stopped_timer_does_nothing() {
+    fn setting_a_stopped_timer_is_refused() {
         let mut timer = testing_timer();
         timer.state = TimerState::Stopped;
         let stopped = timer.clone();
 
-        assert_eq!(timer.set(0, 2).into_iter().count(), 0);
+        assert_eq!(timer.set(0, 2), Err(TimerError::Stopped));
         assert_eq!(timer, stopped);
     }
 
@@ -670,22 +2380,25 @@ mod tests {
             loops: TimerLoop::Fixed(2),
         });
 
-        timer.start(0);
+        timer.start(0).unwrap();
         timer.update(5);
 
-        // The sixth second completes the second loop of a three second
-        // configuration, and the timer used to fall silent there.
+        // The sixth millisecond completes the second loop of a three
+        // millisecond configuration, and the timer used to fall silent
+        // there.
         let events: Vec<_> = timer.update(6).into_iter().collect();
 
         assert_eq!(
             events,
             vec![
-                TimerEvent::Ended(TimerCycle::new("b", 0)),
+                TimerEvent::Ended(TimerCycle::new("b", 0).into()),
+                TimerEvent::LoopEnded(1),
+                TimerEvent::Completed,
                 TimerEvent::Stopped
             ]
         );
         assert_eq!(timer.state, TimerState::Stopped);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 2));
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
         assert_eq!(timer.elapsed, 0);
         assert_eq!(timer.started_at, None);
     }
@@ -697,7 +2410,7 @@ mod tests {
             ..Default::default()
         });
 
-        timer.start(0);
+        timer.start(0).unwrap();
         timer.update(1);
 
         // Comparing names alone missed this boundary, since the cycle
@@ -707,8 +2420,10 @@ mod tests {
         assert_eq!(
             events,
             vec![
-                TimerEvent::Ended(TimerCycle::new("a", 0)),
-                TimerEvent::Began(TimerCycle::new("a", 2)),
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::LoopBegan(1),
+                TimerEvent::Began(TimerCycle::new("a", 2).into()),
             ]
         );
     }
@@ -720,7 +2435,7 @@ mod tests {
             ..Default::default()
         });
 
-        timer.start(0);
+        timer.start(0).unwrap();
         timer.update(1);
 
         let events: Vec<_> = timer.update(2).into_iter().collect();
@@ -728,52 +2443,1106 @@ mod tests {
         assert_eq!(
             events,
             vec![
-                TimerEvent::Ended(TimerCycle::new("a", 0)),
-                TimerEvent::Began(TimerCycle::new("a", 3)),
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("a", 3).into()),
             ]
         );
     }
 
     #[test]
-    fn a_paused_timer_can_be_stopped() {
+    fn skipping_ends_the_cycle_and_begins_the_next() {
         let mut timer = testing_timer();
-        timer.update(2);
-        timer.pause(2);
+        timer.update(1);
 
-        let events: Vec<_> = timer.stop().into_iter().collect();
+        let events = timer.skip(1).unwrap();
 
         assert_eq!(
             events,
             vec![
-                TimerEvent::Ended(TimerCycle::new("a", 1)),
-                TimerEvent::Stopped
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("b", 2).into()),
             ]
         );
-        assert_eq!(timer.state, TimerState::Stopped);
-        assert_eq!(timer.cycle, TimerCycle::new("a", 3));
-        assert_eq!(timer.elapsed, 0);
+
+        // The skip moved the elapsed time, so the next tick carries on
+        // from the start of "b" rather than reverting to "a".
+        assert_eq!(timer.update(1).into_iter().count(), 0);
+        timer.update(2);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 1));
     }
 
     #[test]
-    fn a_stopped_timer_stops_into_nothing() {
+    fn skipping_the_last_cycle_comes_round_to_the_first() {
         let mut timer = testing_timer();
-        timer.state = TimerState::Stopped;
+        timer.update(5);
+        assert_eq!(*timer.cycle, TimerCycle::new("c", 1));
+
+        let events = timer.skip(5).unwrap();
 
-        assert_eq!(timer.stop().into_iter().count(), 0);
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("c", 0).into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::LoopBegan(1),
+                TimerEvent::Began(TimerCycle::new("a", 3).into()),
+            ]
+        );
+        assert_eq!(timer.elapsed, 6);
     }
 
     #[test]
-    fn cycles_lasting_no_time_are_inert() {
+    fn skipping_the_last_cycle_of_the_last_loop_stops() {
         let mut timer = Timer::new(TimerSchedule {
-            cycles: vec![TimerCycle::new("a", 0)],
-            ..Default::default()
+            cycles: vec![TimerCycle::new("a", 2), TimerCycle::new("b", 1)],
+            loops: TimerLoop::Fixed(1),
         });
 
-        timer.start(0);
+        timer.start(0).unwrap();
+        timer.skip(0).unwrap();
 
-        // No elapsed time can name a cycle here, so the tick and the set
-        // both bail out rather than divide by the zero-length loop.
-        assert_eq!(timer.update(10).into_iter().count(), 0);
-        assert_eq!(timer.set(10, 1).into_iter().count(), 0);
+        let events = timer.skip(0).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("b", 0).into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::Completed,
+                TimerEvent::Stopped
+            ]
+        );
+        assert_eq!(timer.state, TimerState::Stopped);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2));
+    }
+
+    #[test]
+    fn a_paused_timer_skips_and_stays_paused() {
+        let mut timer = testing_timer();
+        timer.pause(1).unwrap();
+
+        timer.skip(1).unwrap();
+        assert_eq!(timer.state, TimerState::Paused);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
+
+        timer.resume(10).unwrap();
+        timer.update(11);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 1));
+    }
+
+    #[test]
+    fn skipping_a_stopped_timer_is_refused() {
+        let mut timer = testing_timer();
+        timer.state = TimerState::Stopped;
+
+        assert_eq!(timer.skip(0), Err(TimerError::Stopped));
+    }
+
+    #[test]
+    fn an_extended_cycle_outlasts_its_configured_length() {
+        let mut timer = testing_timer();
+        timer.update(1);
+
+        // Cycle "a" lasts 3 and ran 1, so 10 more cannot be expressed by
+        // moving the elapsed time: the schedule shifts instead.
+        let events = timer.extend(1, 10).unwrap();
+
+        assert_eq!(
+            events,
+            vec![TimerEvent::Extended(TimerCycle::new("a", 12).into())]
+        );
+        assert_eq!(timer.elapsed(1), 1);
+        assert_eq!(timer.extended, 10);
+
+        timer.update(2);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 11));
+
+        timer.update(12);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 1));
+
+        // The cycles after the extended one keep their configured
+        // durations, only later.
+        let events: Vec<_> = timer.update(13).into_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("b", 2).into()),
+            ]
+        );
+
+        timer.update(16);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
+    }
+
+    #[test]
+    fn shortening_a_cycle_stops_at_its_end() {
+        let mut timer = testing_timer();
+
+        let events = timer.extend(0, -1).unwrap();
+        assert_eq!(
+            events,
+            vec![TimerEvent::Extended(TimerCycle::new("a", 2).into())]
+        );
+
+        // Asking for more than remains ends "a" on the next tick, and
+        // leaves "b" whole.
+        let events = timer.extend(0, -60).unwrap();
+        assert_eq!(
+            events,
+            vec![TimerEvent::Extended(TimerCycle::new("a", 0).into())]
+        );
+
+        timer.update(0);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
+    }
+
+    #[test]
+    fn an_extension_holds_across_a_pause_and_a_set() {
+        let mut timer = testing_timer();
+        timer.pause(2).unwrap();
+
+        timer.extend(2, 5).unwrap();
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 6));
+
+        timer.resume(10).unwrap();
+        timer.update(11);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 5));
+
+        // A set stays bound by the configured length, and keeps the
+        // schedule shifted.
+        timer.set(11, 60).unwrap();
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
+        timer.update(14);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
+    }
+
+    #[test]
+    fn extending_beyond_what_the_timer_counts_is_refused() {
+        let mut timer = testing_timer();
+
+        assert_eq!(timer.extend(0, isize::MAX), Err(TimerError::OutOfRange));
+        assert_eq!(timer.extended, 0);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
+
+        // The longest extension that fits still ticks.
+        timer.extend(0, isize::MAX - 3).unwrap();
+        timer.update(1);
+        assert_eq!(timer.cycle.duration, isize::MAX as usize - 1);
+    }
+
+    #[test]
+    fn extending_a_stopped_timer_is_refused() {
+        let mut timer = testing_timer();
+        timer.state = TimerState::Stopped;
+
+        assert_eq!(timer.extend(0, 10), Err(TimerError::Stopped));
+        assert_eq!(timer.extended, 0);
+    }
+
+    #[test]
+    fn going_to_a_cycle_begins_it() {
+        let mut timer = testing_timer();
+        timer.update(1);
+
+        let events = timer.goto(1, 2, None).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 2).into()),
+                TimerEvent::Began(TimerCycle::new("c", 1).into()),
+            ]
+        );
+        assert_eq!(timer.elapsed, 5);
+
+        // The elapsed time now sits at the start of "c", so the ticks
+        // derive it rather than reverting to "a".
+        assert_eq!(timer.update(1).into_iter().count(), 0);
+        timer.update(2);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
+    }
+
+    #[test]
+    fn going_to_a_cycle_starts_a_stopped_timer() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 3), TimerCycle::new("b", 2)],
+            loops: TimerLoop::Fixed(3),
+        });
+
+        let events = timer.goto(10, 1, Some(2)).unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Started,
+                TimerEvent::LoopBegan(2),
+                TimerEvent::Began(TimerCycle::new("b", 2).into())
+            ]
+        );
+        assert_eq!(timer.state, TimerState::Running);
+        assert_eq!(timer.elapsed, 13);
+        assert_eq!(timer.started_at, Some(10));
+
+        // The third loop is the last one, so ending "b" completes it.
+        let events: Vec<_> = timer.update(12).into_iter().collect();
+        assert_eq!(events.last(), Some(&TimerEvent::Stopped));
+    }
+
+    #[test]
+    fn going_out_of_the_schedule_is_refused() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 3)],
+            loops: TimerLoop::Fixed(1),
+        });
+
+        assert_eq!(timer.goto(0, 1, None), Err(TimerError::OutOfSchedule));
+        assert_eq!(timer.goto(0, 0, Some(1)), Err(TimerError::OutOfSchedule));
+        assert_eq!(timer.state, TimerState::Stopped);
+    }
+
+    #[test]
+    fn going_further_than_the_timer_counts_is_refused() {
+        let mut timer = testing_timer();
+
+        // Past what a millisecond count holds, then past what the
+        // timer counts its schedule time in.
+        for loop_index in [usize::MAX / 2, usize::MAX / 6] {
+            assert_eq!(
+                timer.goto(5, 1, Some(loop_index)),
+                Err(TimerError::OutOfSchedule)
+            );
+        }
+
+        // The timer was left as it was, in the first cycle.
+        assert_eq!(timer.state, TimerState::Running);
+        assert_eq!(timer.cycle.name, "a");
+        assert_eq!(timer.elapsed, 0);
+    }
+
+    #[test]
+    fn cycles_are_found_by_index_or_by_name() {
+        let schedule = testing_timer().schedule;
+
+        assert_eq!(schedule.find_cycle(&"1".parse().unwrap()), Some(1));
+        assert_eq!(schedule.find_cycle(&"c".parse().unwrap()), Some(2));
+        assert_eq!(schedule.find_cycle(&TimerCycleRef::Index(3)), None);
+        assert_eq!(schedule.find_cycle(&"d".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn a_paused_timer_can_be_stopped() {
+        let mut timer = testing_timer();
+        timer.update(2);
+        timer.pause(2).unwrap();
+
+        let events = timer.stop().unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 1).into()),
+                TimerEvent::Stopped
+            ]
+        );
+        assert_eq!(timer.state, TimerState::Stopped);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
+        assert_eq!(timer.elapsed, 0);
+    }
+
+    #[test]
+    fn a_refusal_names_what_the_state_lacks() {
+        let mut timer = testing_timer();
+
+        assert_eq!(timer.start(0), Err(TimerError::AlreadyRunning));
+        assert_eq!(timer.resume(0), Err(TimerError::AlreadyRunning));
+
+        timer.pause(1).unwrap();
+        assert_eq!(timer.pause(1), Err(TimerError::NotRunning));
+
+        timer.stop().unwrap();
+        assert_eq!(timer.pause(1), Err(TimerError::Stopped));
+        assert_eq!(timer.resume(1), Err(TimerError::Stopped));
+    }
+
+    #[test]
+    fn stopping_a_stopped_timer_is_refused() {
+        let mut timer = testing_timer();
+        timer.state = TimerState::Stopped;
+
+        assert_eq!(timer.stop(), Err(TimerError::Stopped));
+    }
+
+    #[test]
+    fn cycles_lasting_no_time_are_inert() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 0)],
+            ..Default::default()
+        });
+
+        timer.start(0).unwrap();
+
+        // No elapsed time can name a cycle here, so the tick, the set and
+        // the skip all bail out rather than divide by the zero-length
+        // loop.
+        assert_eq!(timer.update(10).into_iter().count(), 0);
+        assert_eq!(timer.set(10, 1), Err(TimerError::NoTime));
+        assert_eq!(timer.skip(10), Err(TimerError::NoTime));
+    }
+
+    fn manual_timer() -> Timer {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 3).manual(), TimerCycle::new("b", 2)],
+            ..Default::default()
+        });
+        timer.start(0).unwrap();
+        timer
+    }
+
+    #[test]
+    fn manual_cycle_waits_at_its_end() {
+        let mut timer = manual_timer();
+        let events: Vec<_> = timer.update(3).into_iter().collect();
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).manual().into()),
+                TimerEvent::Awaiting(TimerCycle::new("b", 2).into()),
+            ]
+        );
+        assert_eq!(timer.state, TimerState::Waiting);
+
+        // Waiting counts no time, however late the next tick comes.
+        assert_eq!(timer.update(60).into_iter().count(), 0);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
+    }
+
+    #[test]
+    fn a_loop_ending_on_a_wait_begins_the_next_one_on_resume() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 3), TimerCycle::new("b", 2).manual()],
+            ..Default::default()
+        });
+        timer.start(0).unwrap();
+
+        let events: Vec<_> = timer.update(3).into_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("b", 2).manual().into()),
+            ]
+        );
+
+        let events: Vec<_> = timer.update(5).into_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("b", 0).manual().into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::Awaiting(TimerCycle::new("a", 3).into()),
+            ]
+        );
+
+        let events = timer.resume(10).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::LoopBegan(1),
+                TimerEvent::Began(TimerCycle::new("a", 3).into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn waiting_timer_waits_where_the_cycle_ended() {
+        let mut timer = manual_timer();
+
+        // A late tick lands inside the next cycle, which still begins
+        // whole once resumed.
+        timer.update(4);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
+
+        let events = timer.resume(60).unwrap();
+        assert_eq!(
+            events,
+            vec![TimerEvent::Began(TimerCycle::new("b", 2).into())]
+        );
+        assert_eq!(timer.state, TimerState::Running);
+
+        timer.update(61);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 1));
+    }
+
+    #[test]
+    fn waiting_timer_skips_into_the_cycle_it_awaits() {
+        let mut timer = manual_timer();
+        timer.update(3);
+
+        let events = timer.skip(10).unwrap();
+        assert_eq!(
+            events,
+            vec![TimerEvent::Began(TimerCycle::new("b", 2).into())]
+        );
+        assert_eq!(timer.state, TimerState::Running);
+    }
+
+    #[test]
+    fn waiting_timer_stops_without_ending_again() {
+        let mut timer = manual_timer();
+        timer.update(3);
+
+        // Pausing, setting and extending have no running cycle to act on.
+        assert_eq!(timer.pause(10), Err(TimerError::NotRunning));
+        assert_eq!(timer.set(10, 1), Err(TimerError::NotRunning));
+        assert_eq!(timer.extend(10, 60), Err(TimerError::NotRunning));
+
+        let events = timer.stop().unwrap();
+        assert_eq!(events, vec![TimerEvent::Stopped]);
+        assert_eq!(timer.state, TimerState::Stopped);
+    }
+
+    #[test]
+    fn manual_cycle_carries_its_flag_only() {
+        let cycle: TimerCycle =
+            serde_json::from_str(r#"{"name":"a","duration":3,"auto-advance":false}"#).unwrap();
+        assert_eq!(cycle, TimerCycle::new("a", 3000).manual());
+
+        // Cycles advancing on their own keep their usual shape.
+        assert_eq!(
+            serde_json::to_string(&TimerCycle::new("a", 3000)).unwrap(),
+            r#"{"name":"a","duration":3,"duration-ms":3000}"#
+        );
+    }
+
+    fn overtime_timer() -> Timer {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 3).overtime(), TimerCycle::new("b", 5)],
+            ..Default::default()
+        });
+        timer.start(0).unwrap();
+        timer
+    }
+
+    #[test]
+    fn overtime_cycle_keeps_counting_past_its_end() {
+        let mut timer = overtime_timer();
+        let mut events = Vec::new();
+
+        events.extend(timer.update(3));
+        events.extend(timer.update(4));
+        events.extend(timer.update(4));
+        events.extend(timer.update(7));
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).overtime().into()),
+                TimerEvent::Overtime(TimerCycle::new("a", 0).overtime().into()),
+                TimerEvent::Overtime(TimerCycle::new("a", 1).overtime().into()),
+                TimerEvent::Overtime(TimerCycle::new("a", 4).overtime().into()),
+            ]
+        );
+        assert_eq!(timer.state, TimerState::Overtime);
+        assert_eq!(timer.remaining(), -4);
+    }
+
+    #[test]
+    fn overtime_counts_from_the_end_of_the_cycle() {
+        let mut timer = overtime_timer();
+
+        // A late tick lands past the end, and what it skipped counts.
+        timer.update(5);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2).overtime());
+    }
+
+    #[test]
+    fn acknowledged_overtime_shifts_the_schedule() {
+        let mut timer = overtime_timer();
+        timer.update(3);
+        timer.update(10);
+
+        let events = timer.resume(10).unwrap();
+        assert_eq!(
+            events,
+            vec![TimerEvent::Began(TimerCycle::new("b", 5).into())]
+        );
+        assert_eq!(timer.state, TimerState::Running);
+        assert_eq!(timer.extended, 7);
+
+        timer.update(11);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 4));
+
+        timer.update(15);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3).overtime());
+    }
+
+    #[test]
+    fn overtime_is_acknowledged_by_a_skip_too() {
+        let mut timer = overtime_timer();
+        timer.update(4);
+
+        let events = timer.skip(6).unwrap();
+        assert_eq!(
+            events,
+            vec![TimerEvent::Began(TimerCycle::new("b", 5).into())]
+        );
+    }
+
+    #[test]
+    fn overtime_stops_without_ending_again() {
+        let mut timer = overtime_timer();
+        timer.update(4);
+
+        assert_eq!(timer.pause(5), Err(TimerError::NotRunning));
+        assert_eq!(timer.set(5, 1), Err(TimerError::NotRunning));
+
+        let events = timer.stop().unwrap();
+        assert_eq!(events, vec![TimerEvent::Stopped]);
+    }
+
+    fn flowtime_timer() -> Timer {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![
+                TimerCycle::count_up("Work"),
+                TimerCycle::new("Rest", 60).ratio(0.2, Some(600)),
+            ],
+            ..Default::default()
+        });
+        timer.start(0).unwrap();
+        timer
+    }
+
+    #[test]
+    fn count_up_cycle_counts_until_skipped() {
+        let mut timer = flowtime_timer();
+        assert_eq!(*timer.cycle, TimerCycle::count_up("Work"));
+
+        let mut events = Vec::new();
+        events.extend(timer.update(1));
+        events.extend(timer.update(1));
+        events.extend(timer.update(1500));
+
+        let mut work = TimerCycle::count_up("Work");
+        work.duration = 1;
+        let mut later = work.clone();
+        later.duration = 1500;
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Running(work.into()),
+                TimerEvent::Running(later.clone().into())
+            ]
+        );
+        assert_eq!(timer.remaining(), -1500);
+
+        // Nothing but a client ends it, however long it runs.
+        timer.update(100_000);
+        assert_eq!(timer.counting, Some(0));
+        assert_eq!(timer.set(100_000, 60), Err(TimerError::CountingUp));
+        assert_eq!(timer.extend(100_000, 60), Err(TimerError::CountingUp));
+    }
+
+    #[test]
+    fn flowtime_rest_is_a_share_of_the_work() {
+        let mut timer = flowtime_timer();
+        timer.update(1500);
+
+        let events = timer.skip(1500).unwrap();
+
+        let mut work = TimerCycle::count_up("Work");
+        work.duration = 1500;
+        let mut rest = TimerCycle::new("Rest", 300).ratio(0.2, Some(600));
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(work.into()),
+                TimerEvent::Began(rest.clone().into())
+            ]
+        );
+        assert_eq!(timer.counting, None);
+
+        // The rest counts down from its derived duration, and the tick
+        // ending it begins the next count-up cycle.
+        timer.update(1501);
+        rest.duration = 299;
+        assert_eq!(*timer.cycle, rest);
+
+        let events: Vec<_> = timer.update(1800).into_iter().collect();
+        rest.duration = 0;
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(rest.into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::LoopBegan(1),
+                TimerEvent::Began(TimerCycle::count_up("Work").into()),
+            ]
+        );
+        assert_eq!(timer.counting, Some(0));
+
+        timer.update(1810);
+        assert_eq!(timer.cycle.duration, 10);
+    }
+
+    #[test]
+    fn flowtime_rest_is_bounded() {
+        let mut timer = flowtime_timer();
+        timer.skip(100).unwrap();
+        assert_eq!(timer.cycle.duration, 60);
+
+        let mut timer = flowtime_timer();
+        timer.skip(10_000).unwrap();
+        assert_eq!(timer.cycle.duration, 600);
+    }
+
+    #[test]
+    fn a_late_tick_stops_at_a_count_up_cycle() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![
+                TimerCycle::new("a", 3),
+                TimerCycle::count_up("w"),
+                TimerCycle::new("b", 2),
+            ],
+            ..Default::default()
+        });
+        timer.start(0).unwrap();
+
+        // The tick lands inside b, past w, yet w begins where a ended
+        // and counts what the tick skipped.
+        let events: Vec<_> = timer.update(4).into_iter().collect();
+
+        let mut w = TimerCycle::count_up("w");
+        w.duration = 1;
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(w.into()),
+            ]
+        );
+
+        timer.skip(10).unwrap();
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
+
+        timer.update(11);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 1));
+    }
+
+    #[test]
+    fn a_maximum_below_the_duration_leaves_the_duration() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![
+                TimerCycle::count_up("Work"),
+                TimerCycle::new("Rest", 300_000).ratio(0.2, Some(100_000)),
+            ],
+            ..Default::default()
+        });
+        timer.start(0).unwrap();
+
+        timer.skip(3_000_000).unwrap();
+        assert_eq!(timer.cycle.duration, 300_000);
+
+        timer.update(3_100_000);
+        assert_eq!(timer.cycle.duration, 200_000);
+    }
+
+    #[test]
+    fn a_cycle_without_duration_counts_up() {
+        let cycle: TimerCycle = serde_json::from_str(r#"{"name":"Meeting"}"#).unwrap();
+        assert_eq!(cycle, TimerCycle::count_up("Meeting"));
+
+        // The explicit flag says the same, and wins over a duration.
+        let cycle: TimerCycle =
+            serde_json::from_str(r#"{"name":"Meeting","duration":60,"count-up":true}"#).unwrap();
+        assert!(cycle.count_up);
+    }
+
+    #[test]
+    fn a_stopwatch_alone_is_not_inert() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::count_up("Meeting")],
+            ..Default::default()
+        });
+
+        timer.start(0).unwrap();
+        timer.update(30);
+        assert_eq!(timer.cycle.duration, 30);
+
+        let events = timer.skip(30).unwrap();
+
+        let mut meeting = TimerCycle::count_up("Meeting");
+        meeting.duration = 30;
+
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(meeting.into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::LoopBegan(1),
+                TimerEvent::Began(TimerCycle::count_up("Meeting").into()),
+            ]
+        );
+
+        timer.update(40);
+        assert_eq!(timer.cycle.duration, 10);
+
+        // Going to a count-up cycle needs no schedule time either.
+        timer.goto(40, 0, None).unwrap();
+        timer.update(45);
+        assert_eq!(timer.cycle.duration, 5);
+    }
+
+    #[test]
+    fn a_stopwatch_counts_its_loops() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::count_up("Meeting")],
+            loops: TimerLoop::Fixed(2),
+        });
+
+        timer.start(0).unwrap();
+
+        let mut meeting = TimerCycle::count_up("Meeting");
+        meeting.duration = 30;
+
+        assert_eq!(
+            timer.skip(30).unwrap(),
+            vec![
+                TimerEvent::Ended(meeting.clone().into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::LoopBegan(1),
+                TimerEvent::Began(TimerCycle::count_up("Meeting").into()),
+            ]
+        );
+
+        assert_eq!(
+            timer.skip(60).unwrap(),
+            vec![
+                TimerEvent::Ended(meeting.into()),
+                TimerEvent::LoopEnded(1),
+                TimerEvent::Completed,
+                TimerEvent::Stopped,
+            ]
+        );
+        assert_eq!(timer.state, TimerState::Stopped);
+    }
+
+    #[test]
+    fn groups_flatten_into_the_cycle_sequence() {
+        let entries: Vec<TimerEntry> = serde_json::from_str(
+            r#"[
+                {"repeat": 2, "cycles": [
+                    {"name": "Work", "duration": 1500},
+                    {"repeat": 2, "cycles": [{"name": "Rest", "duration": 300}]}
+                ]},
+                {"name": "Long rest", "duration": 1800}
+            ]"#,
+        )
+        .unwrap();
+
+        let cycles = TimerEntry::flatten(&entries);
+        let names: Vec<_> = cycles.iter().map(|cycle| cycle.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Work", "Rest", "Rest", "Work", "Rest", "Rest", "Long rest"]
+        );
+
+        // Each cycle carries where it stands in every group around it,
+        // the outermost first.
+        let iteration = |index, count| TimerIteration { index, count };
+        assert_eq!(cycles[0].iterations, [iteration(0, 2)]);
+        assert_eq!(cycles[5].iterations, [iteration(1, 2), iteration(1, 2)]);
+        assert_eq!(cycles[4].iterations, [iteration(1, 2), iteration(0, 2)]);
+        assert!(cycles[6].iterations.is_empty());
+    }
+
+    #[test]
+    fn the_current_cycle_carries_its_iteration() {
+        let cycles = TimerEntry::flatten(&[TimerEntry::Group(TimerGroup {
+            repeat: 3,
+            cycles: vec![TimerCycle::new("Work", 2).into()],
+        })]);
+        let mut timer = Timer::new(TimerSchedule {
+            cycles,
+            ..Default::default()
+        });
+
+        timer.start(0).unwrap();
+        timer.update(3);
+        assert_eq!(
+            timer.cycle.iterations,
+            [TimerIteration { index: 1, count: 3 }]
+        );
+    }
+
+    #[test]
+    fn a_pause_loses_no_fraction_of_a_second() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 3000), TimerCycle::new("b", 2000)],
+            ..Default::default()
+        });
+
+        // Whole seconds dropped 900ms at the pause and 500ms at the
+        // resume, and a day of pauses drifted by minutes.
+        timer.start(0).unwrap();
+        timer.pause(1900).unwrap();
+        timer.resume(5500).unwrap();
+        timer.update(6500);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 100));
+
+        let events: Vec<_> = timer.update(6600).into_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("b", 2000).into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn durations_travel_in_seconds_and_milliseconds() {
+        // The milliseconds win over the seconds beside them, which an
+        // older peer reads alone, truncated.
+        let cycle: TimerCycle =
+            serde_json::from_str(r#"{"name":"a","duration":1,"duration-ms":1500}"#).unwrap();
+        assert_eq!(cycle, TimerCycle::new("a", 1500));
+
+        let cycle = TimerCycle::new("a", 0).ratio(0.2, Some(2500));
+        let json = serde_json::to_value(&cycle).unwrap();
+        assert_eq!(json["max-duration"], 2);
+        assert_eq!(json["max-duration-ms"], 2500);
+    }
+
+    #[test]
+    fn a_timer_from_an_older_peer_counts_in_seconds() {
+        let timer: Timer = serde_json::from_str(
+            r#"{
+                "schedule": {"cycles": [{"name": "a", "duration": 3}], "loops": "Infinite"},
+                "state": "Running",
+                "cycle": {"name": "a", "duration": 2},
+                "started_at": 1786711298,
+                "elapsed": 1
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(timer.started_at, Some(1_786_711_298_000));
+        assert_eq!(timer.elapsed, 1000);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 2000));
+
+        // Both units go back out, so that peer keeps reading its own.
+        let json = serde_json::to_value(&timer).unwrap();
+        assert_eq!(json["started_at"], 1786711298);
+        assert_eq!(json["started_at_ms"], 1786711298000u64);
+        assert_eq!(json["elapsed"], 1);
+    }
+
+    #[test]
+    fn the_deadline_is_the_end_of_the_cycle() {
+        let mut timer = Timer::new(TimerSchedule {
+            cycles: vec![TimerCycle::new("a", 3000), TimerCycle::new("b", 2000)],
+            ..Default::default()
+        });
+        timer.start(0).unwrap();
+
+        assert_eq!(timer.deadline(250, false), Some(3000));
+
+        // Ticks land where the remaining duration reads whole seconds,
+        // which a set leaving a fraction moves.
+        assert_eq!(timer.deadline(250, true), Some(1000));
+
+        // A tick waking past that second still has it due.
+        assert_eq!(timer.deadline(1001, true), Some(1001));
+
+        timer.set(250, 2500).unwrap();
+        assert_eq!(timer.deadline(250, true), Some(750));
+        assert_eq!(timer.deadline(250, false), Some(2750));
+
+        // Nothing but a client changes a paused timer.
+        timer.pause(250).unwrap();
+        assert_eq!(timer.deadline(250, true), None);
+    }
+
+    #[test]
+    fn a_deadline_the_clock_passed_is_due() {
+        let mut timer = testing_timer();
+
+        // The schedule time already names "b", yet no tick ended "a".
+        assert_eq!(timer.deadline(4, false), Some(4));
+
+        timer.update(4);
+        assert_eq!(timer.deadline(4, false), Some(5));
+    }
+
+    #[test]
+    fn counting_up_has_a_deadline_only_when_ticking() {
+        let mut timer = flowtime_timer();
+
+        assert_eq!(timer.deadline(1250, false), None);
+
+        // No tick reported the first second yet, so it is due.
+        assert_eq!(timer.deadline(1250, true), Some(1250));
+        timer.update(1250);
+        assert_eq!(timer.deadline(1250, true), Some(2000));
+    }
+
+    #[test]
+    fn a_skipping_jump_reports_every_boundary() {
+        let mut timer = testing_timer();
+
+        let began: Vec<_> = timer
+            .jump(1, 14, TimerJump::Skip)
+            .into_iter()
+            .filter_map(|event| match event {
+                TimerEvent::Began(cycle) => Some(cycle.cycle.name),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(began, ["b", "c", "a", "b", "c", "a"]);
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 1));
+    }
+
+    #[test]
+    fn a_counting_jump_reports_the_boundary_it_lands_past() {
+        let mut timer = testing_timer();
+
+        assert_eq!(
+            timer
+                .jump(1, 14, TimerJump::Count)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::LoopEnded(0),
+                TimerEvent::LoopBegan(2),
+                TimerEvent::Began(TimerCycle::new("a", 1).into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_pausing_jump_loses_the_time_jumped_over() {
+        let mut timer = testing_timer();
+
+        // The end of "a" came before the jump, so it is crossed first.
+        assert_eq!(
+            timer
+                .jump(4, 14, TimerJump::Pause)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![
+                TimerEvent::Ended(TimerCycle::new("a", 0).into()),
+                TimerEvent::Began(TimerCycle::new("b", 1).into()),
+                TimerEvent::Paused(TimerCycle::new("b", 1).into()),
+            ]
+        );
+        assert_eq!(timer.elapsed(14), 4);
+    }
+
+    #[test]
+    fn a_clock_set_back_takes_back_no_time() {
+        let mut timer = testing_timer();
+        timer.started_at = Some(10);
+
+        assert!(
+            timer
+                .jump(12, 11, TimerJump::Count)
+                .into_iter()
+                .next()
+                .is_none()
+        );
+        assert_eq!(timer.elapsed(11), 2);
+    }
+
+    #[test]
+    fn a_label_carries_across_cycles_until_the_timer_stops() {
+        let mut timer = testing_timer();
+        timer.state = TimerState::Stopped;
+
+        // A stopped timer keeps the label for the run to come.
+        let task = Some("review".to_string());
+        let tags = vec!["oss".to_string()];
+        assert_eq!(timer.label(task.clone(), tags.clone()), Ok(vec![]));
+
+        let events = timer.start(0).unwrap();
+        let TimerEvent::Began(began) = &events[2] else {
+            panic!("the start began no cycle: {events:?}");
+        };
+        assert_eq!(began.task, task);
+        assert_eq!(began.tags, tags);
+
+        timer.update(3);
+        assert_eq!(timer.cycle.name, "b");
+        assert_eq!(timer.cycle.task, task);
+
+        let events = timer.label(None, vec![]).unwrap();
+        assert_eq!(
+            events,
+            vec![TimerEvent::Labeled(TimerCycle::new("b", 2).into())]
+        );
+
+        timer.label(task, tags).unwrap();
+        timer.stop().unwrap();
+        assert_eq!(*timer.cycle, TimerCycle::new("a", 3));
+    }
+
+    #[test]
+    fn interruptions_stay_with_their_cycle() {
+        let mut timer = testing_timer();
+
+        let events = timer
+            .interrupt(TimerInterruptionKind::External, Some("call".to_string()))
+            .unwrap();
+        let TimerEvent::Interrupted(cycle) = &events[0] else {
+            panic!("the interruption emitted nothing: {events:?}");
+        };
+        assert_eq!(cycle.interruptions_of(TimerInterruptionKind::External), 1);
+
+        // Ticks, sets and pauses recompute the cycle, and keep them.
+        timer
+            .interrupt(TimerInterruptionKind::Internal, None)
+            .unwrap();
+        timer.update(1);
+        timer.set(1, 2).unwrap();
+        timer.pause(1).unwrap();
+        assert_eq!(timer.cycle.interruptions.len(), 2);
+        assert_eq!(
+            timer
+                .cycle
+                .interruptions_of(TimerInterruptionKind::Internal),
+            1
+        );
+
+        // The next cycle begins without any.
+        timer.resume(1).unwrap();
+        let events: Vec<_> = timer.update(3).into_iter().collect();
+        let TimerEvent::Ended(ended) = &events[0] else {
+            panic!("the cycle did not end: {events:?}");
+        };
+        assert_eq!(ended.interruptions.len(), 2);
+        assert_eq!(*timer.cycle, TimerCycle::new("b", 2));
+        assert!(timer.cycle.interruptions.is_empty());
+
+        timer.stop().unwrap();
+        assert_eq!(
+            timer.interrupt(TimerInterruptionKind::Internal, None),
+            Err(TimerError::Stopped)
+        );
+    }
+
+    #[test]
+    fn a_precision_truncates_to_what_it_shows() {
+        assert_eq!(TimerPrecision::Second.truncate(1_499_999), 1_499_000);
+        assert_eq!(TimerPrecision::Minute.truncate(1_499_999), 1_440_000);
+        assert_eq!(TimerPrecision::Hour.truncate(1_499_999), 1_440_000);
+        assert_eq!(TimerPrecision::Hour.truncate(5_400_000), 3_600_000);
+        // Every precision shows the last minute to the second.
+        assert_eq!(TimerPrecision::Hour.truncate(59_999), 59_000);
     }
 }
@@ -8,6 +8,12 @@ use comodoro::{
         config::{Config, LOCALHOST, TCP_PORT},
         transport::Transport,
     },
+    server::{
+        goal::TimerGoal,
+        history::TimerHistory,
+        store::{TimerRestore, TimerStore},
+    },
+    timer::{TimerCycle, TimerJump},
     transport::TimerAddress,
 };
 use pimalaya_config::toml::TomlConfig;
@@ -133,3 +139,168 @@ fn a_server_binds_the_transports_it_is_given() {
         vec![socket, tcp]
     );
 }
+
+#[test]
+fn a_cycle_opts_out_of_auto_advance() {
+    let account = account(
+        r#"
+        [accounts.example]
+        cycles = [
+          { name = "Work", duration = 1500 },
+          { name = "Rest", duration = 300, auto-advance = false },
+        ]
+        "#,
+    );
+
+    assert_eq!(
+        account.schedule.cycles,
+        vec![
+            TimerCycle::new("Work", 1_500_000),
+            TimerCycle::new("Rest", 300_000).manual(),
+        ]
+    );
+}
+
+#[test]
+fn a_cycle_without_duration_is_a_stopwatch() {
+    let account = account(
+        r#"
+        [accounts.example]
+        cycles = [{ name = "Meeting" }]
+        "#,
+    );
+
+    assert_eq!(
+        account.schedule.cycles,
+        vec![TimerCycle::count_up("Meeting")]
+    );
+}
+
+#[test]
+fn a_cycle_lasts_milliseconds_when_asked() {
+    let account = account(
+        r#"
+        [accounts.example]
+        cycles = [
+          { name = "Work", duration = 2 },
+          { name = "Blink", duration-ms = 250 },
+        ]
+        "#,
+    );
+
+    assert_eq!(
+        account.schedule.cycles,
+        vec![TimerCycle::new("Work", 2000), TimerCycle::new("Blink", 250)]
+    );
+}
+
+#[test]
+fn grouped_cycles_flatten_into_the_schedule() {
+    let account = account(
+        r#"
+        [accounts.example]
+        cycles = [
+          { repeat = 2, cycles = [
+            { name = "Work", duration = 1500 },
+            { name = "Rest", duration = 300 },
+          ] },
+          { name = "Long rest", duration = 1800 },
+        ]
+        "#,
+    );
+
+    let names: Vec<_> = account
+        .schedule
+        .cycles
+        .iter()
+        .map(|cycle| cycle.name.as_str())
+        .collect();
+
+    assert_eq!(names, ["Work", "Rest", "Work", "Rest", "Long rest"]);
+}
+
+#[test]
+fn a_schedule_running_no_cycle_is_refused() {
+    let schedules = [
+        ("cycles = []", "holds no cycle"),
+        (
+            r#"cycles = [{ repeat = 0, cycles = [{ name = "Work", duration = 1500 }] }]"#,
+            "repeat at least once",
+        ),
+        (
+            r#"cycles = [{ repeat = 2, cycles = [{ repeat = 1, cycles = [] }] }]"#,
+            "hold at least one cycle",
+        ),
+    ];
+
+    for (cycles, reason) in schedules {
+        let toml = format!("[accounts.example]\n{cycles}\n");
+        let err = toml::from_str::<Config>(&toml).unwrap_err();
+        assert!(err.to_string().contains(reason), "{err}");
+    }
+}
+
+#[test]
+fn a_clock_jump_policy_reads_from_the_account() {
+    let account = account(
+        r#"
+        [accounts.example]
+        clock-jump = "skip"
+        cycles = [{ name = "Work", duration = 1500 }]
+        "#,
+    );
+
+    assert_eq!(account.clock_jump, TimerJump::Skip);
+}
+
+#[test]
+fn state_and_history_tables_keep_what_the_server_ran() {
+    let account = account(
+        r#"
+        [accounts.example]
+        state.path = "/tmp/comodoro.json"
+        state.restore = "pause"
+        state.stale-after = 3600
+        history.path = "/tmp/comodoro.jsonl"
+        cycles = [{ name = "Work", duration = 1500 }]
+        "#,
+    );
+
+    assert_eq!(
+        account.state,
+        Some(TimerStore {
+            path: "/tmp/comodoro.json".into(),
+            restore: TimerRestore::Pause,
+            stale_after: Some(3_600_000),
+        })
+    );
+    assert_eq!(
+        account.history,
+        Some(TimerHistory {
+            path: "/tmp/comodoro.jsonl".into(),
+        })
+    );
+}
+
+#[test]
+fn a_goal_counts_the_first_cycle_from_its_own_day_start() {
+    let account = account(
+        r#"
+        [accounts.example]
+        cycles = [{ name = "Work", duration = 1500 }, { name = "Break", duration = 300 }]
+        goal.duration = 18000
+        goal.day-start = "04:00"
+        goal.utc-offset = "+02:00"
+        "#,
+    );
+
+    assert_eq!(
+        account.goal,
+        Some(TimerGoal {
+            cycle: "Work".into(),
+            cycles: None,
+            duration: Some(18_000_000),
+            day_start: 7_200_000,
+        })
+    );
+}
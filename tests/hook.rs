@@ -10,7 +10,7 @@ use std::{
 
 use comodoro::{
     cli::hook::TimerHook,
-    timer::{TimerCycle, TimerEvent},
+    timer::{TimerCycle, TimerCycleRun, TimerEvent},
 };
 
 static NEXT_PATH: AtomicUsize = AtomicUsize::new(0);
@@ -35,7 +35,7 @@ fn a_string_command_runs_through_a_shell() {
     // The redirection is the point: it only works if a shell parses the
     // line rather than the kernel executing it as a program name.
     let mut hook = hook(&format!(r#"command = "echo hooked >> {}""#, path.display()));
-    hook.execute();
+    hook.execute(&TimerEvent::Started);
 
     assert_eq!(fs::read_to_string(&path).unwrap().trim(), "hooked");
     let _ = fs::remove_file(path);
@@ -49,12 +49,36 @@ fn an_array_command_executes_with_no_shell() {
     // A shell would read the `>` as a redirection. Executed directly,
     // it is one more character in the file name.
     let mut hook = hook(&format!(r#"command = ["touch", "{}"]"#, path.display()));
-    hook.execute();
+    hook.execute(&TimerEvent::Started);
 
     assert!(path.exists(), "{}", path.display());
     let _ = fs::remove_file(path);
 }
 
+#[cfg(unix)]
+#[test]
+fn a_command_reads_the_task_from_its_environment() {
+    let path = path("task");
+    let line = format!(
+        r#"command = "echo \"$COMODORO_TASK|$COMODORO_TAGS\" >> {}""#,
+        path.display()
+    );
+    let mut hook = hook(&line);
+
+    let mut cycle = TimerCycleRun::from(TimerCycle::new("Work", 1_500_000));
+    cycle.task = Some("review PR 42".to_string());
+    cycle.tags = vec!["review".to_string(), "oss".to_string()];
+    hook.execute(&TimerEvent::Began(cycle));
+
+    // NOTE: the same hook fires again for an event carrying no task,
+    // which must not inherit the one before it.
+    hook.execute(&TimerEvent::Stopped);
+
+    let lines = fs::read_to_string(&path).unwrap();
+    assert_eq!(lines, "review PR 42|review,oss\n|\n");
+    let _ = fs::remove_file(path);
+}
+
 // A hook is a reaction, never a step of the timer, so the two ways one
 // can fail are logged and returned from as if nothing happened. The
 // assertion is the absence of a panic: there is no error left to catch,
@@ -64,12 +88,12 @@ fn an_array_command_executes_with_no_shell() {
 #[cfg(unix)]
 #[test]
 fn a_command_exiting_non_zero_is_inert() {
-    hook(r#"command = ["false"]"#).execute();
+    hook(r#"command = ["false"]"#).execute(&TimerEvent::Started);
 }
 
 #[test]
 fn a_missing_program_is_inert() {
-    hook(r#"command = ["comodoro-hook-does-not-exist"]"#).execute();
+    hook(r#"command = ["comodoro-hook-does-not-exist"]"#).execute(&TimerEvent::Started);
 }
 
 #[cfg(feature = "notify")]
@@ -104,10 +128,14 @@ fn an_empty_command_is_refused() {
 
 #[test]
 fn events_name_the_hook_they_fire() {
-    let cycle = TimerCycle::new("Long rest", 1800);
+    let cycle = TimerCycleRun::from(TimerCycle::new("Long rest", 1_800_000));
 
     assert_eq!(TimerEvent::Started.hook_name(), "on-timer-start");
     assert_eq!(TimerEvent::Stopped.hook_name(), "on-timer-stop");
+    assert_eq!(TimerEvent::Completed.hook_name(), "on-timer-complete");
+    assert_eq!(TimerEvent::LoopBegan(1).hook_name(), "on-loop-begin");
+    assert_eq!(TimerEvent::LoopEnded(0).hook_name(), "on-loop-end");
+    assert_eq!(TimerEvent::GoalReached.hook_name(), "on-goal-reached");
     assert_eq!(
         TimerEvent::Began(cycle.clone()).hook_name(),
         "on-long-rest-begin"
@@ -120,6 +148,10 @@ fn events_name_the_hook_they_fire() {
         TimerEvent::Set(cycle.clone()).hook_name(),
         "on-long-rest-set"
     );
+    assert_eq!(
+        TimerEvent::Extended(cycle.clone()).hook_name(),
+        "on-long-rest-extend"
+    );
     assert_eq!(
         TimerEvent::Paused(cycle.clone()).hook_name(),
         "on-long-rest-pause"
@@ -128,5 +160,16 @@ fn events_name_the_hook_they_fire() {
         TimerEvent::Resumed(cycle.clone()).hook_name(),
         "on-long-rest-resume"
     );
-    assert_eq!(TimerEvent::Ended(cycle).hook_name(), "on-long-rest-end");
+    assert_eq!(
+        TimerEvent::Ended(cycle.clone()).hook_name(),
+        "on-long-rest-end"
+    );
+    assert_eq!(
+        TimerEvent::Awaiting(cycle.clone()).hook_name(),
+        "on-long-rest-await"
+    );
+    assert_eq!(
+        TimerEvent::Overtime(cycle).hook_name(),
+        "on-long-rest-overtime"
+    );
 }
@@ -5,24 +5,46 @@
 use std::{
     env, fs,
     path::PathBuf,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::Receiver,
+    },
     thread,
     time::Duration,
 };
 
 use comodoro::{
     client::std::TimerClient,
-    server::std::TimerServer,
-    timer::{TimerCycle, TimerEvent, TimerLoop, TimerSchedule, TimerState},
+    jsonrpc20::Jsonrpc20Error,
+    protocol::{
+        TimerCompatibility, TimerEventFilter, TimerLabelParams, TimerProtocolVersion, TimerRequest,
+        TimerServerInfo, TimerSubscribeParams, TimerTicks,
+    },
+    server::{
+        clock::{SystemClock, TimerClock, TimerWake, VirtualClock},
+        goal::TimerGoal,
+        history::{TimerEnd, TimerHistory, TimerRecord},
+        std::TimerServer,
+        store::{TimerRestore, TimerStore},
+    },
+    timer::{
+        Timer, TimerCycle, TimerCycleRef, TimerError, TimerEvent, TimerInterruption,
+        TimerInterruptionKind, TimerJump, TimerLoop, TimerPrecision, TimerSchedule, TimerState,
+    },
     transport::TimerAddress,
 };
+use serde_json::json;
 
 static NEXT_SOCKET: AtomicUsize = AtomicUsize::new(0);
 
 /// The cycles every server in this file runs.
 fn schedule() -> TimerSchedule {
     TimerSchedule {
-        cycles: vec![TimerCycle::new("Work", 1500), TimerCycle::new("Break", 300)],
+        cycles: vec![
+            TimerCycle::new("Work", 1_500_000),
+            TimerCycle::new("Break", 300_000),
+        ],
         loops: TimerLoop::Infinite,
     }
 }
@@ -30,6 +52,12 @@ fn schedule() -> TimerSchedule {
 /// Starts a server on a socket unique to this test, and returns a
 /// client connected to it.
 fn serve() -> (TimerClient, PathBuf) {
+    serve_on(Arc::new(SystemClock), None)
+}
+
+/// Starts a server running on `clock` and keeping its timer in
+/// `store` the way [`serve`] does.
+fn serve_on(clock: Arc<dyn TimerClock>, store: Option<TimerStore>) -> (TimerClient, PathBuf) {
     let id = NEXT_SOCKET.fetch_add(1, Ordering::Relaxed);
     let path = env::temp_dir().join(format!("comodoro-test-{}-{id}.sock", std::process::id()));
     let _ = fs::remove_file(&path);
@@ -37,8 +65,9 @@ fn serve() -> (TimerClient, PathBuf) {
     let address = TimerAddress::UnixSocket(path.clone());
 
     let events = TimerServer {
-        schedule: schedule(),
-        addresses: vec![address.clone()],
+        clock,
+        store,
+        ..TimerServer::new(schedule(), vec![address.clone()])
     }
     .serve()
     .expect("serve timer");
@@ -66,19 +95,49 @@ fn start_returns_started_then_began() {
 
     let events = client.start().unwrap();
 
-    assert_eq!(events.len(), 2);
+    assert_eq!(events.len(), 3);
     assert_eq!(events[0], TimerEvent::Started);
-    assert!(matches!(events[1], TimerEvent::Began(_)));
+    assert_eq!(events[1], TimerEvent::LoopBegan(0));
+    assert!(matches!(events[2], TimerEvent::Began(_)));
     assert_eq!(client.get().unwrap().state, TimerState::Running);
     let _ = fs::remove_file(path);
 }
 
+/// The refusal a failed call carries, read back from its code.
+fn refusal(err: anyhow::Error) -> TimerError {
+    let err = err.downcast::<Jsonrpc20Error>().unwrap();
+    TimerError::from_jsonrpc(&err).unwrap()
+}
+
+#[test]
+fn an_extension_beyond_what_the_timer_counts_is_invalid_params() {
+    let (mut client, path) = serve();
+    client.start().unwrap();
+
+    // Too large for milliseconds, then too large for the remaining
+    // time the cycle has already.
+    for delta in [isize::MAX, isize::MAX / 1000] {
+        let err = client.extend(delta).unwrap_err();
+        let err = err.downcast::<Jsonrpc20Error>().unwrap();
+        assert_eq!(err.code, Jsonrpc20Error::INVALID_PARAMS);
+        assert_eq!(err.data, Some(json!(["delta"])));
+    }
+
+    // The timer was left as it was, and still ticks.
+    let timer = client.get().unwrap();
+    assert_eq!(timer.extended, 0);
+    assert_eq!(timer.cycle.name, "Work");
+    let _ = fs::remove_file(path);
+}
+
 #[test]
-fn start_on_a_running_timer_is_a_noop() {
+fn start_on_a_running_timer_is_refused() {
     let (mut client, path) = serve();
 
     client.start().unwrap();
-    assert!(client.start().unwrap().is_empty());
+    let err = client.start().unwrap_err();
+
+    assert_eq!(refusal(err), TimerError::AlreadyRunning);
     let _ = fs::remove_file(path);
 }
 
@@ -97,10 +156,27 @@ fn stop_returns_ended_then_stopped() {
 }
 
 #[test]
-fn stop_on_a_stopped_timer_is_a_noop() {
+fn stop_on_a_stopped_timer_is_refused() {
+    let (mut client, path) = serve();
+
+    assert_eq!(refusal(client.stop().unwrap_err()), TimerError::Stopped);
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn a_refusal_carries_its_code_and_the_state_that_refused() {
     let (mut client, path) = serve();
 
-    assert!(client.stop().unwrap().is_empty());
+    client.start().unwrap();
+    client.pause().unwrap();
+    let err = client.pause().unwrap_err();
+    let err = err.downcast::<Jsonrpc20Error>().unwrap();
+
+    assert_eq!(err.code, TimerError::NOT_RUNNING);
+    assert_eq!(
+        err.data,
+        Some(json!({ "error": "notRunning", "state": "Paused" }))
+    );
     let _ = fs::remove_file(path);
 }
 
@@ -124,22 +200,162 @@ fn pause_then_resume_round_trips_the_state() {
 
 #[test]
 fn set_overrides_the_remaining_duration() {
-    let (mut client, path) = serve();
+    let clock = VirtualClock::new(0);
+    let (mut client, path) = serve_on(Arc::new(clock.clone()), None);
     client.start().unwrap();
 
     let events = client.set(60).unwrap();
 
     assert_eq!(events.len(), 1);
     assert!(matches!(events[0], TimerEvent::Set(_)));
-    assert_eq!(client.get().unwrap().cycle.duration, 60);
+    assert_eq!(client.get().unwrap().cycle.duration, 60_000);
+
+    // Reading the timer recomputes the cycle from the elapsed time, as
+    // ticks do, which used to discard what was set. Reading later is the
+    // whole point of this assertion.
+    clock.advance(1500);
+    assert_eq!(client.get().unwrap().cycle.duration, 58_500);
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn skip_begins_the_next_cycle() {
+    let (mut client, path) = serve();
+    client.start().unwrap();
+
+    let events = client.skip().unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert!(matches!(events[0], TimerEvent::Ended(_)));
+    assert_eq!(
+        events[1],
+        TimerEvent::Began(TimerCycle::new("Break", 300_000).into())
+    );
+    assert_eq!(client.get().unwrap().cycle.name, "Break");
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn extend_lengthens_the_cycle_beyond_its_configuration() {
+    let clock = VirtualClock::new(0);
+    let (mut client, path) = serve_on(Arc::new(clock.clone()), None);
+    client.start().unwrap();
+
+    let events = client.extend(600).unwrap();
+
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], TimerEvent::Extended(_)));
+
+    // Read later, which recomputes the cycle from the elapsed time and
+    // would discard an extension kept anywhere else.
+    clock.advance(1500);
+    let timer = client.get().unwrap();
+    assert_eq!(timer.cycle.name, "Work");
+    assert_eq!(timer.cycle.duration, 2_098_500);
+    assert_eq!(timer.extended, 600_000);
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn goto_starts_a_stopped_timer_at_the_named_cycle() {
+    let (mut client, path) = serve();
+
+    let events = client
+        .goto(TimerCycleRef::Name("Break".into()), Some(2))
+        .unwrap();
+
+    assert_eq!(
+        events,
+        vec![
+            TimerEvent::Started,
+            TimerEvent::LoopBegan(2),
+            TimerEvent::Began(TimerCycle::new("Break", 300_000).into())
+        ]
+    );
+
+    let timer = client.get().unwrap();
+    assert_eq!(timer.state, TimerState::Running);
+    assert_eq!(timer.elapsed, (2 * 1800 + 1500) * 1000);
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn goto_refuses_a_cycle_the_schedule_does_not_hold() {
+    let (mut client, path) = serve();
+
+    let err = client
+        .goto(TimerCycleRef::Name("Nap".into()), None)
+        .unwrap_err();
+
+    assert!(err.to_string().contains("-32602"), "{err}");
+    assert_eq!(client.get().unwrap().state, TimerState::Stopped);
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn goto_refuses_a_loop_further_than_the_timer_counts() {
+    let (mut client, path) = serve();
+
+    let err = client
+        .goto(TimerCycleRef::Name("Break".into()), Some(usize::MAX / 1000))
+        .unwrap_err();
+    let err = err.downcast::<Jsonrpc20Error>().unwrap();
+
+    assert_eq!(err.code, Jsonrpc20Error::INVALID_PARAMS);
+    assert_eq!(err.data, Some(json!(["loop"])));
+    assert_eq!(client.get().unwrap().state, TimerState::Stopped);
+    let _ = fs::remove_file(path);
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn discover_describes_every_method_the_server_answers() {
+    let (mut client, path) = serve();
+
+    let document = client.discover().unwrap();
+    let methods: Vec<&str> = document["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|method| method["name"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(document["openrpc"], "1.3.2");
+    assert_eq!(methods, TimerRequest::METHODS);
+    assert!(document["components"]["schemas"]["Timer"].is_object());
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn info_names_the_version_methods_and_features() {
+    let (mut client, path) = serve();
 
-    // The server ticks every second, and the tick used to recompute the
-    // cycle from the elapsed time and discard what was set. Reading
-    // across one is the whole point of this assertion.
-    thread::sleep(Duration::from_millis(1500));
-    let duration = client.get().unwrap().cycle.duration;
-    assert!((57..=59).contains(&duration), "{duration}");
+    let info = client.info().unwrap();
 
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(info.protocol, TimerProtocolVersion::CURRENT);
+    assert_eq!(info.methods, TimerRequest::METHODS);
+    assert_eq!(info.features, [TimerServerInfo::SOCKET]);
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn a_checked_connection_keeps_what_the_server_said() {
+    let (client, path) = serve();
+    let address = TimerAddress::UnixSocket(path.clone());
+    drop(client);
+
+    let client = TimerClient::connect_checked(&address).unwrap();
+
+    assert_eq!(client.compatibility(), Some(TimerCompatibility::Full));
+    assert_eq!(
+        client.server_info().unwrap().protocol,
+        TimerProtocolVersion::CURRENT
+    );
+    assert!(client.supports(TimerRequest::INTERRUPT));
+    assert!(!client.supports("timer.later"));
     let _ = fs::remove_file(path);
 }
 
@@ -167,6 +383,7 @@ fn a_subscriber_receives_the_events_its_own_calls_emit() {
     client.start().unwrap();
 
     assert_eq!(client.next_event().unwrap(), Some(TimerEvent::Started));
+    assert_eq!(client.next_event().unwrap(), Some(TimerEvent::LoopBegan(0)));
     assert!(matches!(
         client.next_event().unwrap(),
         Some(TimerEvent::Began(_))
@@ -184,6 +401,10 @@ fn a_subscriber_receives_the_events_another_client_emits() {
     driver.start().unwrap();
 
     assert_eq!(watcher.next_event().unwrap(), Some(TimerEvent::Started));
+    assert_eq!(
+        watcher.next_event().unwrap(),
+        Some(TimerEvent::LoopBegan(0))
+    );
     assert!(matches!(
         watcher.next_event().unwrap(),
         Some(TimerEvent::Began(_))
@@ -191,6 +412,45 @@ fn a_subscriber_receives_the_events_another_client_emits() {
     let _ = fs::remove_file(path);
 }
 
+#[test]
+fn a_filtered_subscriber_receives_only_what_it_asked_for() {
+    let (mut watcher, path) = serve();
+    let address = TimerAddress::UnixSocket(path.clone());
+    let mut driver = TimerClient::connect(&address).expect("connect a second client");
+
+    watcher
+        .subscribe_filtered(TimerSubscribeParams {
+            include: TimerEventFilter {
+                events: vec!["began".to_string(), "ended".to_string()],
+                cycles: Vec::new(),
+            },
+            exclude: TimerEventFilter {
+                events: Vec::new(),
+                cycles: vec!["Break".to_string()],
+            },
+            ticks: None,
+        })
+        .unwrap();
+
+    driver.start().unwrap();
+    driver.skip().unwrap();
+    driver.skip().unwrap();
+
+    // Neither the start, the loops nor anything about the break comes
+    // through, so the second work cycle follows the first one's end.
+    let work = |event: Option<TimerEvent>| event.unwrap().cycle().unwrap().name.clone();
+    let began = watcher.next_event().unwrap();
+    assert!(matches!(began, Some(TimerEvent::Began(_))));
+    assert_eq!(work(began), "Work");
+    let ended = watcher.next_event().unwrap();
+    assert!(matches!(ended, Some(TimerEvent::Ended(_))));
+    assert_eq!(work(ended), "Work");
+    let began = watcher.next_event().unwrap();
+    assert!(matches!(began, Some(TimerEvent::Began(_))));
+    assert_eq!(work(began), "Work");
+    let _ = fs::remove_file(path);
+}
+
 #[test]
 fn unsubscribing_stops_the_notifications_but_not_the_requests() {
     let (mut client, path) = serve();
@@ -214,12 +474,9 @@ fn unsubscribing_stops_the_notifications_but_not_the_requests() {
 fn a_second_server_refuses_a_socket_in_use() {
     let (_client, path) = serve();
 
-    let err = TimerServer {
-        schedule: schedule(),
-        addresses: vec![TimerAddress::UnixSocket(path.clone())],
-    }
-    .serve()
-    .unwrap_err();
+    let err = TimerServer::new(schedule(), vec![TimerAddress::UnixSocket(path.clone())])
+        .serve()
+        .unwrap_err();
 
     assert!(err.to_string().contains("already in use"), "{err}");
     let _ = fs::remove_file(path);
@@ -241,8 +498,10 @@ fn both_transports_serve_the_same_timer() {
     };
 
     let events = TimerServer {
-        schedule: schedule(),
-        addresses: vec![socket.clone(), tcp.clone()],
+        // NOTE: a read answers the timer as of its call, so two reads
+        // compare equal only on a clock that stands still between them.
+        clock: Arc::new(VirtualClock::new(0)),
+        ..TimerServer::new(schedule(), vec![socket.clone(), tcp.clone()])
     }
     .serve()
     .expect("serve timer");
@@ -270,24 +529,436 @@ fn a_second_server_refuses_a_port_in_use() {
         port: 47822,
     };
 
+    let events = TimerServer::new(schedule(), vec![address.clone()])
+        .serve()
+        .expect("serve timer");
+
+    thread::spawn(move || while events.recv().is_ok() {});
+
+    let err = TimerServer::new(schedule(), vec![address])
+        .serve()
+        .unwrap_err();
+
+    assert!(
+        err.to_string().contains("Bind socket 127.0.0.1:47822"),
+        "{err}"
+    );
+}
+
+/// A virtual clock recording every wait the server asks it for.
+#[derive(Clone, Default)]
+struct WaitClock {
+    clock: VirtualClock,
+    waits: Arc<Mutex<Vec<u64>>>,
+}
+
+impl WaitClock {
+    /// Blocks until the server asked to wait `ms`, failing rather than
+    /// waiting on a server that never does.
+    fn await_wait(&self, ms: u64) {
+        for _ in 0..200 {
+            if self.waits.lock().unwrap().last() == Some(&ms) {
+                return;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        panic!("no wait of {ms}ms in {:?}", self.waits.lock().unwrap());
+    }
+}
+
+impl TimerClock for WaitClock {
+    fn now(&self) -> u64 {
+        self.clock.now()
+    }
+
+    fn monotonic(&self) -> u64 {
+        self.clock.monotonic()
+    }
+
+    fn timeout(&self, ms: u64) -> Duration {
+        self.waits.lock().unwrap().push(ms);
+        self.clock.timeout(ms)
+    }
+
+    fn register(&self, wake: TimerWake) {
+        self.clock.register(wake);
+    }
+}
+
+#[test]
+fn a_cycle_end_reaches_the_hooks_on_time() {
+    let id = NEXT_SOCKET.fetch_add(1, Ordering::Relaxed);
+    let path = env::temp_dir().join(format!("comodoro-test-{}-{id}.sock", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let address = TimerAddress::UnixSocket(path.clone());
+    let clock = WaitClock::default();
+
+    let schedule = TimerSchedule {
+        cycles: vec![
+            TimerCycle::new("Blink", 300),
+            TimerCycle::new("Work", 60_000),
+        ],
+        loops: TimerLoop::Infinite,
+    };
     let events = TimerServer {
-        schedule: schedule(),
-        addresses: vec![address.clone()],
+        clock: Arc::new(clock.clone()),
+        ..TimerServer::new(schedule, vec![address.clone()])
     }
     .serve()
-    .expect("serve timer");
+    .unwrap();
 
-    thread::spawn(move || while events.recv().is_ok() {});
+    let mut client = TimerClient::connect(&address).unwrap();
+    client.start().unwrap();
+
+    assert_eq!(next(&events), TimerEvent::Started);
+    assert_eq!(next(&events), TimerEvent::LoopBegan(0));
+    assert!(matches!(next(&events), TimerEvent::Began(_)));
+
+    // The server sleeps until the cycle ends rather than polling every
+    // second, so it waits for the end, and for what is left of it once
+    // woken early.
+    clock.await_wait(300);
+    clock.clock.advance(299);
+    clock.await_wait(1);
+    assert!(events.try_recv().is_err());
 
-    let err = TimerServer {
-        schedule: schedule(),
-        addresses: vec![address],
+    clock.clock.advance(1);
+    let ended = next(&events);
+    assert!(matches!(ended, TimerEvent::Ended(_)), "{ended:?}");
+
+    let _ = fs::remove_file(path);
+}
+
+/// Starts a server running on `clock` with `jump`, recording in
+/// `history` and counting towards `goal` if any, and returns the
+/// receiver of its events with a client connected to it.
+fn serve_events(
+    clock: &VirtualClock,
+    jump: TimerJump,
+    history: Option<TimerHistory>,
+    goal: Option<TimerGoal>,
+) -> (Receiver<TimerEvent>, TimerClient, PathBuf) {
+    let id = NEXT_SOCKET.fetch_add(1, Ordering::Relaxed);
+    let path = env::temp_dir().join(format!("comodoro-test-{}-{id}.sock", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let address = TimerAddress::UnixSocket(path.clone());
+
+    let events = TimerServer {
+        jump,
+        clock: Arc::new(clock.clone()),
+        history,
+        goal,
+        ..TimerServer::new(schedule(), vec![address.clone()])
     }
     .serve()
-    .unwrap_err();
+    .unwrap();
 
-    assert!(
-        err.to_string().contains("Bind socket 127.0.0.1:47822"),
-        "{err}"
+    let client = TimerClient::connect(&address).unwrap();
+    (events, client, path)
+}
+
+/// The next event the server emits, failing rather than waiting on a
+/// server that emits nothing.
+fn next(events: &Receiver<TimerEvent>) -> TimerEvent {
+    events.recv_timeout(Duration::from_secs(2)).unwrap()
+}
+
+#[test]
+fn a_virtual_clock_runs_a_day_of_cycles_at_once() {
+    let clock = VirtualClock::new(0);
+    let (events, mut client, path) = serve_events(&clock, TimerJump::Count, None, None);
+    client.start().unwrap();
+
+    assert_eq!(next(&events), TimerEvent::Started);
+    assert_eq!(next(&events), TimerEvent::LoopBegan(0));
+    assert!(matches!(next(&events), TimerEvent::Began(_)));
+
+    // 48 loops of 25 and 5 minutes make a day.
+    for index in 0..48 {
+        clock.advance(1_500_000);
+        assert!(matches!(next(&events), TimerEvent::Ended(_)));
+        assert_eq!(
+            next(&events),
+            TimerEvent::Began(TimerCycle::new("Break", 300_000).into())
+        );
+
+        clock.advance(300_000);
+        assert!(matches!(next(&events), TimerEvent::Ended(_)));
+        assert_eq!(next(&events), TimerEvent::LoopEnded(index));
+        assert_eq!(next(&events), TimerEvent::LoopBegan(index + 1));
+        assert_eq!(
+            next(&events),
+            TimerEvent::Began(TimerCycle::new("Work", 1_500_000).into())
+        );
+    }
+
+    assert_eq!(client.get().unwrap().elapsed(clock.now()), 86_400_000);
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn a_subscriber_receives_only_the_ticks_changing_what_it_shows() {
+    let clock = VirtualClock::new(0);
+    let (events, mut driver, path) = serve_events(&clock, TimerJump::Count, None, None);
+    let address = TimerAddress::UnixSocket(path.clone());
+    let mut watcher = TimerClient::connect(&address).unwrap();
+
+    watcher
+        .subscribe_filtered(TimerSubscribeParams {
+            include: TimerEventFilter {
+                events: vec!["running".to_string(), "paused".to_string()],
+                cycles: Vec::new(),
+            },
+            ticks: Some(TimerTicks::Precision(TimerPrecision::Minute)),
+            ..Default::default()
+        })
+        .unwrap();
+
+    driver.start().unwrap();
+    assert_eq!(next(&events), TimerEvent::Started);
+    assert_eq!(next(&events), TimerEvent::LoopBegan(0));
+    assert!(matches!(next(&events), TimerEvent::Began(_)));
+
+    // The hook receiver takes every tick, so waiting for each one there
+    // makes sure the watcher was offered it too.
+    for (ms, remaining) in [(1_000, 1_499_000), (58_000, 1_441_000), (2_000, 1_439_000)] {
+        clock.advance(ms);
+        assert_eq!(
+            next(&events),
+            TimerEvent::Running(TimerCycle::new("Work", remaining).into())
+        );
+    }
+
+    driver.pause().unwrap();
+
+    // 24min, 24min then 23min: the second tick shows nothing new.
+    assert_eq!(
+        watcher.next_event().unwrap(),
+        Some(TimerEvent::Running(
+            TimerCycle::new("Work", 1_499_000).into()
+        ))
+    );
+    assert_eq!(
+        watcher.next_event().unwrap(),
+        Some(TimerEvent::Running(
+            TimerCycle::new("Work", 1_439_000).into()
+        ))
+    );
+    assert!(matches!(
+        watcher.next_event().unwrap(),
+        Some(TimerEvent::Paused(_))
+    ));
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn a_suspend_reports_every_cycle_it_slept_through_when_asked() {
+    let clock = VirtualClock::new(0);
+    let (events, mut client, path) = serve_events(&clock, TimerJump::Skip, None, None);
+    client.start().unwrap();
+
+    assert_eq!(next(&events), TimerEvent::Started);
+    assert_eq!(next(&events), TimerEvent::LoopBegan(0));
+    assert!(matches!(next(&events), TimerEvent::Began(_)));
+
+    // An hour passes on the wall clock alone, as it does for a machine
+    // suspended meanwhile: two loops of Work and Break.
+    clock.set(3_600_000);
+
+    let mut began = Vec::new();
+    let mut loops = Vec::new();
+
+    while began.len() < 4 {
+        match next(&events) {
+            TimerEvent::Began(cycle) => began.push(cycle.cycle.name),
+            TimerEvent::LoopBegan(index) => loops.push(index),
+            _ => (),
+        }
+    }
+
+    assert_eq!(began, ["Break", "Work", "Break", "Work"]);
+    assert_eq!(loops, [1, 2]);
+    let _ = fs::remove_file(path);
+}
+
+/// A store unique to this test, restoring as `restore` says.
+fn store(restore: TimerRestore, stale_after: Option<u64>) -> TimerStore {
+    let id = NEXT_SOCKET.fetch_add(1, Ordering::Relaxed);
+    let path = env::temp_dir().join(format!("comodoro-test-{}-{id}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    TimerStore {
+        path,
+        restore,
+        stale_after,
+    }
+}
+
+/// Starts a server on `store`, runs its timer for a minute, then
+/// starts another server on the same store and reads the timer it
+/// took back.
+fn restart(store: &TimerStore) -> Timer {
+    let clock = VirtualClock::new(0);
+
+    let (mut client, path) = serve_on(Arc::new(clock.clone()), Some(store.clone()));
+    client.start().unwrap();
+    let _ = fs::remove_file(path);
+
+    clock.advance(60_000);
+
+    let (mut client, path) = serve_on(Arc::new(clock.clone()), Some(store.clone()));
+    let timer = client.get().unwrap();
+    let _ = fs::remove_file(path);
+    let _ = fs::remove_file(&store.path);
+    timer
+}
+
+#[test]
+fn a_restarted_server_resumes_the_timer_it_saved() {
+    let timer = restart(&store(TimerRestore::Resume, None));
+
+    assert_eq!(timer.state, TimerState::Running);
+    assert_eq!(*timer.cycle, TimerCycle::new("Work", 1_440_000));
+}
+
+#[test]
+fn a_restarted_server_can_take_its_timer_back_paused() {
+    let timer = restart(&store(TimerRestore::Pause, None));
+
+    // Paused as of the save, which the start triggered, so the minute
+    // the server was down counts for nothing.
+    assert_eq!(timer.state, TimerState::Paused);
+    assert_eq!(*timer.cycle, TimerCycle::new("Work", 1_500_000));
+}
+
+#[test]
+fn a_restarted_server_discards_a_stale_timer() {
+    let timer = restart(&store(TimerRestore::Resume, Some(30_000)));
+
+    assert_eq!(timer.state, TimerState::Stopped);
+}
+
+#[test]
+fn every_cycle_ending_lands_in_the_history() {
+    let id = NEXT_SOCKET.fetch_add(1, Ordering::Relaxed);
+    let history = TimerHistory {
+        path: env::temp_dir().join(format!("comodoro-test-{}-{id}.jsonl", std::process::id())),
+    };
+    let _ = fs::remove_file(&history.path);
+
+    let clock = VirtualClock::new(0);
+    let (events, mut client, path) =
+        serve_events(&clock, TimerJump::Count, Some(history.clone()), None);
+
+    client
+        .start_labeled(TimerLabelParams {
+            task: Some("review PR 42".into()),
+            tags: vec!["oss".into()],
+        })
+        .unwrap();
+    clock.advance(60_000);
+    client
+        .interrupt(TimerInterruption {
+            kind: TimerInterruptionKind::External,
+            note: Some("phone call".into()),
+        })
+        .unwrap();
+    client.pause().unwrap();
+    clock.advance(30_000);
+    client.resume().unwrap();
+    clock.advance(1_440_000);
+
+    // The server records a cycle before it reports its end.
+    while !matches!(next(&events), TimerEvent::Began(cycle) if cycle.name == "Break") {}
+
+    clock.advance(100_000);
+    client.skip().unwrap();
+
+    assert_eq!(
+        history.read().unwrap(),
+        vec![
+            TimerRecord {
+                cycle: "Work".into(),
+                began_at_ms: 0,
+                ended_at_ms: 1_530_000,
+                planned_ms: Some(1_500_000),
+                actual_ms: 1_500_000,
+                pauses: 1,
+                paused_ms: 30_000,
+                end: TimerEnd::Completed,
+                task: Some("review PR 42".into()),
+                tags: vec!["oss".into()],
+                interruptions: vec![TimerInterruption {
+                    kind: TimerInterruptionKind::External,
+                    note: Some("phone call".into()),
+                }],
+            },
+            TimerRecord {
+                cycle: "Break".into(),
+                began_at_ms: 1_530_000,
+                ended_at_ms: 1_630_000,
+                planned_ms: Some(300_000),
+                actual_ms: 100_000,
+                pauses: 0,
+                paused_ms: 0,
+                end: TimerEnd::Skipped,
+                task: Some("review PR 42".into()),
+                tags: vec!["oss".into()],
+                interruptions: vec![],
+            },
+        ]
     );
+
+    let _ = fs::remove_file(path);
+    let _ = fs::remove_file(&history.path);
+}
+
+#[test]
+fn a_goal_is_reached_once_a_day() {
+    let goal = TimerGoal {
+        cycle: "Work".into(),
+        cycles: Some(2),
+        duration: None,
+        day_start: 0,
+    };
+
+    let clock = VirtualClock::new(0);
+    let (events, mut client, path) = serve_events(&clock, TimerJump::Count, None, Some(goal));
+    client.start().unwrap();
+
+    assert_eq!(next(&events), TimerEvent::Started);
+    assert_eq!(next(&events), TimerEvent::LoopBegan(0));
+    assert!(matches!(next(&events), TimerEvent::Began(_)));
+
+    let mut reached = 0;
+
+    // Three loops of Work and Break, the goal asking for two Work.
+    for _ in 0..6 {
+        clock.advance(client.get().unwrap().cycle.duration as u64);
+
+        loop {
+            match next(&events) {
+                TimerEvent::GoalReached => reached += 1,
+                TimerEvent::Began(_) => break,
+                _ => (),
+            }
+        }
+    }
+
+    let progress = client.get().unwrap().goal.unwrap();
+    assert_eq!(reached, 1);
+    assert_eq!(progress.cycles, 3);
+    assert_eq!(progress.duration_ms, 4_500_000);
+    assert!(progress.reached);
+
+    // The next day starts the count over, before any cycle ends.
+    client.stop().unwrap();
+    clock.set(86_400_000);
+    let progress = client.get().unwrap().goal.unwrap();
+    assert_eq!((progress.cycles, progress.reached), (0, false));
+    assert_eq!(progress.since_ms, 86_400_000);
+
+    let _ = fs::remove_file(path);
 }